@@ -494,6 +494,9 @@ public ref partial struct SliceEncoder
 
     /// <summary>Encodes a fixed-size numeric value.</summary>
     /// <param name="v">The numeric value to encode.</param>
+    /// <remarks>The Slice encoding always encodes fixed-size numeric values in little-endian byte order, which is not
+    /// configurable. This method copies the value's in-memory representation and therefore assumes a little-endian
+    /// platform.</remarks>
     internal void EncodeFixedSizeNumeric<T>(T v) where T : struct
     {
         int elementSize = Unsafe.SizeOf<T>();
@@ -708,6 +708,36 @@ public partial class OperationTests
         }
     }
 
+    [Test]
+    public async Task Operation_with_value_task_returns_the_return_value()
+    {
+        // Arrange
+        var invoker = new ColocInvoker(new MyValueTaskOperationsService());
+        var proxy = new MyValueTaskOperationsProxy(invoker);
+
+        // Act
+        ValueTask<int> task = proxy.OpWithValueTaskAsync(10);
+
+        // Assert
+        Assert.That(await task, Is.EqualTo(10));
+    }
+
+    [Test]
+    public async Task Operation_with_value_task_and_void_return_completes()
+    {
+        // Arrange
+        var service = new MyValueTaskOperationsService();
+        var invoker = new ColocInvoker(service);
+        var proxy = new MyValueTaskOperationsProxy(invoker);
+
+        // Act
+        ValueTask task = proxy.OpWithValueTaskAndVoidReturnAsync();
+        await task;
+
+        // Assert
+        Assert.That(service.Called, Is.True);
+    }
+
     [SliceService]
     private partial class MyOperationsAService : IMyOperationsAService
     {
@@ -905,4 +935,23 @@ public partial class OperationTests
         public ValueTask OpAsync(IFeatureCollection features, CancellationToken cancellationToken) => default;
         public ValueTask OpDerivedAsync(IFeatureCollection features, CancellationToken cancellationToken) => default;
     }
+
+    [SliceService]
+    private sealed partial class MyValueTaskOperationsService : IMyValueTaskOperationsService
+    {
+        internal bool Called { get; private set; }
+
+        public ValueTask<int> OpWithValueTaskAsync(
+            int p,
+            IFeatureCollection features,
+            CancellationToken cancellationToken) => new(p);
+
+        public ValueTask OpWithValueTaskAndVoidReturnAsync(
+            IFeatureCollection features,
+            CancellationToken cancellationToken)
+        {
+            Called = true;
+            return default;
+        }
+    }
 }
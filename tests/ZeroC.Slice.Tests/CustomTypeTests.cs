@@ -219,6 +219,109 @@ public class CustomTypeTests
         Assert.That(decoder.DecodeUInt8(), Is.EqualTo(Slice1Definitions.TagEndMarker));
         Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
     }
+
+    [Test]
+    public void Encode_custom_type_as_bytes([Values] SliceEncoding encoding)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, encoding);
+        var expected = new StructWithBytesCustomTypeField(new MyBytesCustomType("hello"));
+
+        // Act
+        expected.Encode(ref encoder);
+
+        // Assert
+        var decoder = new SliceDecoder(buffer.WrittenMemory, encoding);
+        Assert.That(decoder.DecodeSize(), Is.EqualTo(5));
+        Assert.That(decoder.Consumed + 5, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Decode_custom_type_from_bytes([Values] SliceEncoding encoding)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, encoding);
+        var expected = new StructWithBytesCustomTypeField(new MyBytesCustomType("hello"));
+        expected.Encode(ref encoder);
+        var decoder = new SliceDecoder(buffer.WrittenMemory, encoding);
+
+        // Act
+        var value = new StructWithBytesCustomTypeField(ref decoder);
+
+        // Assert
+        Assert.That(value, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Encode_and_decode_nullable_custom_type_as_bytes([Values("hello", null)] string? text)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice1);
+        MyBytesCustomType? expected = text is null ? null : new MyBytesCustomType(text);
+
+        // Act
+        encoder.EncodeNullableBytesCustomType(expected);
+        var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice1);
+        MyBytesCustomType? value = decoder.DecodeNullableBytesCustomType();
+
+        // Assert
+        Assert.That(value, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Encode_and_decode_struct_with_optional_bytes_custom_type_field(
+        [Values] SliceEncoding encoding,
+        [Values("hello", null)] string? text)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, encoding);
+        var expected = new StructWithOptionalBytesCustomTypeField(text is null ? null : new MyBytesCustomType(text));
+
+        // Act
+        expected.Encode(ref encoder);
+        var decoder = new SliceDecoder(buffer.WrittenMemory, encoding);
+        var value = new StructWithOptionalBytesCustomTypeField(ref decoder);
+
+        // Assert
+        Assert.That(value, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Encode_and_decode_class_with_tagged_bytes_custom_type_field([Values("hello", null)] string? text)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice1);
+        MyBytesCustomType? expected = text is null ? null : new MyBytesCustomType(text);
+
+        // Act
+        encoder.EncodeClass(new ClassWithTaggedBytesCustomTypeField(expected));
+        var decoder = new SliceDecoder(
+            buffer.WrittenMemory,
+            SliceEncoding.Slice1,
+            activator: IActivator.FromAssembly(typeof(ClassWithTaggedBytesCustomTypeField).Assembly));
+        var value = decoder.DecodeClass<ClassWithTaggedBytesCustomTypeField>();
+
+        // Assert
+        Assert.That(value.M, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+}
+
+public record struct MyBytesCustomType(string Text);
+
+public static class MyBytesCustomTypeConverter
+{
+    public static byte[] EncodeBytes(MyBytesCustomType value) => System.Text.Encoding.UTF8.GetBytes(value.Text);
+
+    public static MyBytesCustomType DecodeBytes(byte[] bytes) => new(System.Text.Encoding.UTF8.GetString(bytes));
 }
 
 public record struct MyCustomType
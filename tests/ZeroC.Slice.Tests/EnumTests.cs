@@ -54,6 +54,54 @@ public class EnumTests
     public void Enum_has_the_expected_size(int size, int expectedSize) =>
         Assert.That(size, Is.EqualTo(expectedSize));
 
+    [TestCase("Enum1", MyEnum.Enum1)]
+    [TestCase("Enum3", MyEnum.Enum3)]
+    public void Try_parse_enumerator_name(string name, MyEnum expected)
+    {
+        bool parsed = MyEnumParser.TryParse(name.AsSpan(), out MyEnum value);
+
+        Assert.That(parsed, Is.True);
+        Assert.That(value, Is.EqualTo(expected));
+    }
+
+    [TestCase("enum1")]
+    [TestCase("Enum4")]
+    [TestCase("")]
+    public void Try_parse_fails_for_unknown_enumerator_name(string name)
+    {
+        bool parsed = MyEnumParser.TryParse(name.AsSpan(), out MyEnum value);
+
+        Assert.That(parsed, Is.False);
+        Assert.That(value, Is.EqualTo(default(MyEnum)));
+    }
+
+    [Test]
+    public void Parse_enumerator_name() =>
+        Assert.That(MyEnumParser.Parse("Enum3", provider: null), Is.EqualTo(MyEnum.Enum3));
+
+    [Test]
+    public void Parse_fails_for_unknown_enumerator_name() =>
+        Assert.That(() => MyEnumParser.Parse("Enum4", provider: null), Throws.TypeOf<FormatException>());
+
+    [Test]
+    public void Try_parse_fails_for_null_string() =>
+        Assert.That(MyEnumParser.TryParse(null, provider: null, out _), Is.False);
+
+    [TestCase("Green", MyCaseInsensitiveEnum.Green)]
+    [TestCase("gReEn", MyCaseInsensitiveEnum.Green)]
+    [TestCase("RED", MyCaseInsensitiveEnum.Red)]
+    public void Try_parse_case_insensitive_enumerator_name(string name, MyCaseInsensitiveEnum expected)
+    {
+        bool parsed = MyCaseInsensitiveEnumParser.TryParse(name.AsSpan(), out MyCaseInsensitiveEnum value);
+
+        Assert.That(parsed, Is.True);
+        Assert.That(value, Is.EqualTo(expected));
+    }
+
+    [Test]
+    public void Try_parse_case_insensitive_fails_for_unknown_enumerator_name() =>
+        Assert.That(MyCaseInsensitiveEnumParser.TryParse("Blue".AsSpan(), out _), Is.False);
+
     [Test]
     public void Cs_attribute_on_enumerator()
     {
@@ -165,6 +165,27 @@ public class EnumWithFieldsTests
         }
     }
 
+    [Test]
+    public void Match_calls_the_function_of_the_decoded_enumerator()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        encoder.EncodePaintColor(new PaintColor.Yellow("canary", 7));
+        var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+        PaintColor decoded = decoder.DecodePaintColor();
+
+        // Act
+        string description = decoded.Match(
+            red => "red",
+            white => "white",
+            blue => "blue",
+            yellow => $"yellow {yellow.Shade}");
+
+        // Assert
+        Assert.That(description, Is.EqualTo("yellow canary"));
+    }
+
     [Test]
     public void Enumerator_with_fields_gets_attribute() =>
         Assert.That(typeof(ShapeWithAttribute.Rectangle).GetSliceTypeId(), Is.EqualTo("MyRectangle"));
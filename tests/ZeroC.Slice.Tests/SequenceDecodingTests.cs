@@ -136,6 +136,25 @@ public class SequenceDecodingTests
             Throws.InstanceOf<InvalidDataException>());
     }
 
+    [Test]
+    public void Decode_sequence_with_size_larger_than_int_max_value_fails()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        encoder.EncodeVarUInt62((ulong)int.MaxValue + 1);
+        encoder.WriteByteSpan(new byte[] { 0x00, 0x01, 0x00 });
+
+        // Act/Assert
+        Assert.That(
+            () =>
+            {
+                var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+                _ = new BoolS(ref decoder);
+            },
+            Throws.InstanceOf<InvalidDataException>().With.InnerException.InstanceOf<OverflowException>());
+    }
+
     private enum TestEnum : short
     {
         A = 1,
@@ -1,6 +1,9 @@
 // Copyright (c) ZeroC, Inc.
 
 using NUnit.Framework;
+using System.Buffers;
+using System.IO.Pipelines;
+using System.Runtime.InteropServices;
 
 namespace ZeroC.Slice.Tests;
 
@@ -202,4 +205,259 @@ public sealed class StructTests
         Assert.That(keyValuePair.Key, Is.EqualTo(5));
         Assert.That(keyValuePair.Value, Is.EqualTo("bar"));
     }
+
+    [Test]
+    public void Encode_framed_struct_prefixes_its_fields_with_their_size()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var expected = new MyFramedStruct(10, "hello");
+
+        // Act
+        expected.Encode(ref encoder);
+
+        // Assert
+        var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+        int frameSize = decoder.DecodeInt32();
+        Assert.That(frameSize, Is.EqualTo(buffer.WrittenMemory.Length - 4));
+        Assert.That(decoder.DecodeInt32(), Is.EqualTo(10));
+        Assert.That(decoder.DecodeString(), Is.EqualTo("hello"));
+        Assert.That(decoder.DecodeVarInt32(), Is.EqualTo(Slice2Definitions.TagEndMarker));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Decode_framed_struct()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var expected = new MyFramedStruct(10, "hello");
+        expected.Encode(ref encoder);
+        var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+
+        // Act
+        var decoded = new MyFramedStruct(ref decoder);
+
+        // Assert
+        Assert.That(decoded, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Decode_framed_struct_with_invalid_frame_size_fails()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        encoder.EncodeInt32(100);
+        encoder.EncodeInt32(10);
+        encoder.EncodeString("hello");
+        encoder.EncodeVarInt32(Slice2Definitions.TagEndMarker);
+
+        // Act/Assert
+        Assert.That(
+            () =>
+            {
+                var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+                _ = new MyFramedStruct(ref decoder);
+            },
+            Throws.InstanceOf<InvalidDataException>());
+    }
+
+    [Test]
+    public void Encode_reordered_struct_encodes_fields_in_their_encode_order()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var expected = new MyReorderedStruct(10, "hello", 20);
+
+        // Act
+        expected.Encode(ref encoder);
+
+        // Assert
+        var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+        var bitSequenceReader = decoder.GetBitSequenceReader(1);
+        Assert.That(decoder.DecodeString(), Is.EqualTo("hello"));
+        Assert.That(bitSequenceReader.Read(), Is.True);
+        Assert.That(decoder.DecodeInt32(), Is.EqualTo(20));
+        Assert.That(decoder.DecodeInt32(), Is.EqualTo(10));
+        Assert.That(decoder.DecodeVarInt32(), Is.EqualTo(Slice2Definitions.TagEndMarker));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public void Decode_reordered_struct([Values(20, null)] int? j)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var expected = new MyReorderedStruct(10, "hello", j);
+        expected.Encode(ref encoder);
+        var decoder = new SliceDecoder(buffer.WrittenMemory, SliceEncoding.Slice2);
+
+        // Act
+        var decoded = new MyReorderedStruct(ref decoder);
+
+        // Assert
+        Assert.That(decoded, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(buffer.WrittenMemory.Length));
+    }
+
+    [Test]
+    public async Task Decode_struct_from_pipe_reader_waits_for_the_remaining_bytes()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var expected = new MyPipeReaderStruct(10, "hello");
+        expected.Encode(ref encoder);
+        var pipe = new Pipe();
+        await pipe.Writer.WriteAsync(buffer.WrittenMemory[..3]);
+
+        // Act
+        var decodeTask = pipe.Reader.DecodeMyPipeReaderStructAsync().AsTask();
+        Assert.That(decodeTask.IsCompleted, Is.False);
+        await pipe.Writer.WriteAsync(buffer.WrittenMemory[3..]);
+        await pipe.Writer.WriteAsync(new byte[] { 0xFF });
+
+        // Assert
+        Assert.That(await decodeTask, Is.EqualTo(expected));
+        ReadResult readResult = await pipe.Reader.ReadAsync();
+        Assert.That(readResult.Buffer.ToArray(), Is.EqualTo(new byte[] { 0xFF }));
+    }
+
+    [Test]
+    public async Task Decode_struct_from_completed_pipe_reader_with_truncated_value_fails()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        new MyPipeReaderStruct(10, "hello").Encode(ref encoder);
+        var pipe = new Pipe();
+        await pipe.Writer.WriteAsync(buffer.WrittenMemory[..^1]);
+        pipe.Writer.Complete();
+
+        // Act/Assert
+        Assert.That(
+            async () => await pipe.Reader.DecodeMyPipeReaderStructAsync(),
+            Throws.InstanceOf<InvalidDataException>());
+    }
+
+    [Test]
+    public void Try_encode_struct_into_large_enough_buffer([Values(0, 10)] int extraLength)
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var value = new MyTryEncodeStruct(10, "hello");
+        value.Encode(ref encoder);
+        var destination = new byte[buffer.WrittenMemory.Length + extraLength];
+
+        // Act
+        bool encoded = value.TryEncode(destination, out int bytesWritten);
+
+        // Assert
+        Assert.That(encoded, Is.True);
+        Assert.That(bytesWritten, Is.EqualTo(buffer.WrittenMemory.Length));
+        Assert.That(destination[..bytesWritten], Is.EqualTo(buffer.WrittenMemory.ToArray()));
+    }
+
+    [Test]
+    public void Decode_struct_encoded_with_try_encode()
+    {
+        // Arrange
+        var expected = new MyTryEncodeStruct(10, "hello");
+        var destination = new byte[256];
+        bool encoded = expected.TryEncode(destination, out int bytesWritten);
+        var decoder = new SliceDecoder(destination.AsMemory(0, bytesWritten), SliceEncoding.Slice2);
+
+        // Act
+        var decoded = new MyTryEncodeStruct(ref decoder);
+
+        // Assert
+        Assert.That(encoded, Is.True);
+        Assert.That(decoded, Is.EqualTo(expected));
+        Assert.That(decoder.Consumed, Is.EqualTo(bytesWritten));
+    }
+
+    [Test]
+    public void Try_encode_struct_into_too_small_buffer_fails()
+    {
+        // Arrange
+        var buffer = new MemoryBufferWriter(new byte[256]);
+        var encoder = new SliceEncoder(buffer, SliceEncoding.Slice2);
+        var value = new MyTryEncodeStruct(10, "hello");
+        value.Encode(ref encoder);
+        var destination = new byte[buffer.WrittenMemory.Length - 1];
+
+        // Act
+        bool encoded = value.TryEncode(destination, out int bytesWritten);
+
+        // Assert
+        Assert.That(encoded, Is.False);
+        Assert.That(bytesWritten, Is.EqualTo(0));
+        Assert.That(destination, Is.All.EqualTo(0));
+    }
+
+    [Test]
+    public void Deconstruct_struct_into_its_fields()
+    {
+        // Arrange
+        var value = new MyDeconstructStruct(10, "hello");
+
+        // Act
+        (int i, string s) = value;
+
+        // Assert
+        Assert.That(i, Is.EqualTo(10));
+        Assert.That(s, Is.EqualTo("hello"));
+    }
+
+    [Test]
+    public void Construct_validated_struct_with_invalid_fields_fails() =>
+        Assert.That(() => new MyValidatedStruct(2, 1), Throws.ArgumentException);
+
+    [Test]
+    public void With_expression_on_validated_struct_sets_several_fields_at_once()
+    {
+        // Arrange
+        var value = new MyValidatedStruct(1, 2);
+
+        // Act
+        MyValidatedStruct copy = value with { Min = 3, Max = 4 };
+
+        // Assert
+        Assert.That(copy.Min, Is.EqualTo(3));
+        Assert.That(copy.Max, Is.EqualTo(4));
+    }
+
+    [Test]
+    public void Copy_constructor_of_validated_struct_validates_the_copied_fields()
+    {
+        // Arrange
+        var value = new MyValidatedStruct(1, 2);
+        value.Min = 3;
+
+        // Act/Assert
+        Assert.That(() => value with { }, Throws.ArgumentException);
+    }
+
+    [TestCase(typeof(MySequentialLayoutStruct), LayoutKind.Sequential)]
+    [TestCase(typeof(MyAutoLayoutStruct), LayoutKind.Auto)]
+    public void Struct_layout_attribute_sets_the_layout_kind(Type type, LayoutKind layoutKind) =>
+        Assert.That(type.StructLayoutAttribute!.Value, Is.EqualTo(layoutKind));
+}
+
+public sealed partial record class MyValidatedStruct
+{
+    partial void Validate()
+    {
+        if (Min > Max)
+        {
+            throw new ArgumentException($"{nameof(Min)} must not be greater than {nameof(Max)}.");
+        }
+    }
 }
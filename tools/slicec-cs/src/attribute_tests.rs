@@ -1,11 +1,30 @@
 // Copyright (c) ZeroC, Inc.
 
+use crate::cs_compile::{cs_patcher, cs_validator};
+use crate::cs_options::CsOptions;
+use slicec::ast::Ast;
+use slicec::compilation_state::CompilationState;
+
+/// Compiles the provided Slice files with the provided options, asserts that no errors were emitted, and returns the
+/// resulting compilation state.
+pub fn compile_without_errors(slices: &[&str], options: &CsOptions) -> CompilationState {
+    let state = slicec::compile_from_strings(slices, Some(&options.slice_options), cs_patcher, cs_validator);
+    assert!(!state.diagnostics.has_errors());
+    state
+}
+
+/// Compiles the provided Slice file with the default options, asserts that no errors were emitted, and returns the
+/// resulting AST.
+pub fn compile_for_ast(slice: impl Into<String>) -> Ast {
+    compile_without_errors(&[&slice.into()], &CsOptions::default()).ast
+}
+
 mod attributes {
 
     use crate::cs_attributes::*;
-    use crate::cs_compile::{cs_patcher, cs_validator};
-    use crate::cs_options::CsOptions;
-    use slicec::diagnostics::{Diagnostic, Error};
+    use crate::cs_compile::{check_target_framework_support, cs_patcher, cs_validator};
+    use crate::cs_options::{CsOptions, TargetFramework};
+    use slicec::diagnostics::{Diagnostic, DiagnosticLevel, Error, Lint};
     use slicec::test_helpers::{check_diagnostics, diagnostics_from_compilation_state};
     use test_case::test_case;
 
@@ -204,4 +223,1233 @@ mod attributes {
 
         check_diagnostics(diagnostics, [expected]);
     }
+
+    #[test]
+    fn init_attribute_on_struct_field() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::init]
+                x: int32
+            }
+        ";
+
+        // Act / Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn init_attribute_on_class_field_fails() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class C {
+                [cs::init]
+                x: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsInit::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn required_attribute_on_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::required]
+                x: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsRequired::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("Sequential")]
+    #[test_case("Auto")]
+    fn struct_layout_attribute_on_compact_struct(layout_kind: &str) {
+        let slice = format!(
+            "
+            module Test
+
+            [cs::structLayout({layout_kind})]
+            compact struct S {{
+                x: int32
+                y: float64
+            }}
+            "
+        );
+
+        assert_parses(slice);
+    }
+
+    #[test_case("Packed")]
+    #[test_case("Explicit")]
+    fn struct_layout_attribute_with_invalid_argument_fails(layout_kind: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::structLayout({layout_kind})]
+            compact struct S {{
+                x: int32
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ArgumentNotSupported {
+            argument: layout_kind.to_owned(),
+            directive: CsStructLayout::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn struct_layout_attribute_on_struct_with_non_blittable_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::structLayout(Sequential)]
+            compact struct S {
+                x: int32
+                y: string
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsStructLayout::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn identifier_attribute_collision_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::identifier(\"Name\")]
+                x: int32
+                name: string
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Redefinition {
+            identifier: "Name".to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn fields_with_the_same_slice_identifier_are_only_reported_once() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                name: int32
+                name: string
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Redefinition {
+            identifier: "name".to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("", "fieldTags: int32"; "renamed by case conversion")]
+    #[test_case("", "[cs::identifier(\"FieldTags\")] x: int32"; "renamed by identifier attribute")]
+    #[test_case("compact", "fieldTags: int32"; "compact struct")]
+    fn field_mapped_to_field_tags_fails(modifier: &str, field: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::fieldTags]
+            {modifier} struct S {{
+                {field}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Redefinition {
+            identifier: "FieldTags".to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn field_mapped_to_field_tags_without_field_tags_attribute() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                fieldTags: int32
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn field_tags_attribute_on_operation_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface I {
+                [cs::fieldTags]
+                op()
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsFieldTags::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn cs_attribute_on_struct_and_operation() {
+        let slice = "
+            module Test
+
+            [cs::attribute(\"Foo\")]
+            [cs::attribute(\"Bar(1)\")]
+            struct S {}
+
+            interface I {
+                [cs::attribute(\"Foo\")]
+                op()
+            }
+        ";
+
+        assert_parses(slice);
+    }
+
+    #[test_case("\"\"", ""; "empty")]
+    #[test_case("\"Foo\nBar\"", "Foo\nBar"; "newline")]
+    fn cs_attribute_with_invalid_argument_fails(argument: &str, attribute: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::attribute({argument})]
+            struct S {{}}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ArgumentNotSupported {
+            argument: attribute.to_owned(),
+            directive: CsAttribute::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn value_equality_skip_tagged_attribute_on_compact_struct_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::valueEqualitySkipTagged]
+            compact struct S {
+                x: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsValueEqualitySkipTagged::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn comparable_attribute_on_struct_with_sequence_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::comparable]
+            struct S {
+                x: int32
+                y: sequence<int32>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsComparable::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn class_attribute_on_readonly_struct_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::class]
+            [cs::readonly]
+            struct S {
+                x: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsClass::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn encoded_size_attribute_on_struct_with_dictionary_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::encodedSize]
+            struct S {
+                x: int32
+                y: dictionary<int32, string>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsEncodedSize::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn stable_hash_attribute_on_struct_with_dictionary_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::stableHash]
+            struct S {
+                x: string
+                y: dictionary<int32, string>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsStableHash::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn stable_hash_attribute_on_struct_with_nested_struct_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct Nested {
+                name: string
+            }
+
+            [cs::stableHash]
+            struct S {
+                x: int32
+                y: Nested
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsStableHash::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn stable_hash_attribute_on_struct_with_stable_nested_struct_field() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::stableHash]
+            struct Nested {
+                name: string
+            }
+
+            [cs::stableHash]
+            struct S {
+                x: int32
+                y: Nested
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn flags_attribute_on_enum_with_non_power_of_two_values_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::flags]
+            enum E : int32 {
+                A = 1
+                B = 2
+                C = 3
+                D = 6
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsFlags::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn case_insensitive_attribute_on_enum_with_fields_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::caseInsensitive]
+            enum Shape {
+                Circle(radius: float64)
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsCaseInsensitive::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn internal_types_cannot_be_used_by_public_operations() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::internal]
+            struct S {}
+
+            interface I {
+                op(s: S)
+                opReturn() -> Sequence<S>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = [
+            Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: CsInternal::directive().to_owned(),
+            }),
+            Diagnostic::new(Error::UnexpectedAttribute {
+                attribute: CsInternal::directive().to_owned(),
+            }),
+        ];
+
+        check_diagnostics(diagnostics, expected);
+    }
+
+    #[test]
+    fn internal_types_can_be_used_by_internal_operations() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::internal]
+            struct S {}
+
+            [cs::internal]
+            interface I {
+                op(s: S) -> S
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        check_diagnostics(diagnostics, []);
+    }
+
+    #[test]
+    fn if_defined_attribute_on_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::ifDefined(\"DEBUG\")]
+                a: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsIfDefined::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("Sequence<string>"; "non-blittable elements")]
+    #[test_case("Sequence<bool>"; "bool elements")]
+    #[test_case("Sequence<int32>?"; "optional sequence")]
+    #[test_case("Dictionary<int32, int32>"; "dictionary")]
+    fn memory_attribute_on_unsupported_type_fails(data_type: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            struct S {{
+                a: [cs::memory] {data_type}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsMemory::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("Sequence<string>"; "sequence")]
+    #[test_case("string"; "string")]
+    #[test_case("[cs::type(\"Foo\")] Dictionary<int32, int32>"; "dictionary with cs::type")]
+    fn frozen_attribute_on_unsupported_type_fails(data_type: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            struct S {{
+                a: [cs::frozen] {data_type}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsFrozen::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("struct S {\n[cs::readonlyList]\na: Sequence<int32>?\n}"; "optional sequence")]
+    #[test_case("struct S {\n[cs::readonlyList]\na: int32\n}"; "non-sequence")]
+    #[test_case("enum E {\nA([cs::readonlyList] a: Sequence<int32>)\n}"; "enumerator field")]
+    fn readonly_list_attribute_on_unsupported_field_fails(definition: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            {definition}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsReadonlyList::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn custom_field_types_require_converters() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::type(\"global::System.Guid\")]
+                [cs::encode(\"GuidConverter.Encode\")]
+                id: Sequence<uint8>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::MissingRequiredAttribute {
+            attribute: CsDecode::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn converters_require_custom_field_types() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::decode(\"GuidConverter.Decode\")]
+                id: Sequence<uint8>
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsDecode::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn bytes_converters_on_custom_type_parse() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::type(\"MyType\")]
+            [cs::encodeBytes(\"MyConverter.EncodeBytes\")]
+            [cs::decodeBytes(\"MyConverter.DecodeBytes\")]
+            custom MyCustom
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn bytes_converters_require_both_converters() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::type(\"MyType\")]
+            [cs::decodeBytes(\"MyConverter.DecodeBytes\")]
+            custom MyCustom
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::MissingRequiredAttribute {
+            attribute: CsEncodeBytes::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("encodeBytes(\"\")", "decodeBytes(\"MyConverter.DecodeBytes\")", "", "cs::encodeBytes"; "encode")]
+    #[test_case("encodeBytes(\"MyConverter.EncodeBytes\")", "decodeBytes(\" \")", " ", "cs::decodeBytes"; "decode")]
+    fn bytes_converters_with_empty_method_fail(encode: &str, decode: &str, method: &str, directive: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::type(\"MyType\")]
+            [cs::{encode}]
+            [cs::{decode}]
+            custom MyCustom
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ArgumentNotSupported {
+            argument: method.to_owned(),
+            directive: directive.to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn bytes_converters_require_custom_types() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::encodeBytes(\"MyConverter.EncodeBytes\")]
+            struct S {}
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsEncodeBytes::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn fast_equals_attribute_on_class_struct_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::class]
+            [cs::fastEquals]
+            struct S {}
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsFastEquals::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn sealed_attribute_on_struct_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::sealed]
+            struct S {}
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsSealed::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("class", "class Derived : Base {}"; "class")]
+    #[test_case("exception", "exception Derived : Base {}"; "exception")]
+    fn deriving_from_sealed_type_fails(kind: &str, derived: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            mode = Slice1
+            module Test
+
+            [cs::sealed]
+            {kind} Base {{}}
+
+            {derived}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsSealed::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn value_task_attribute_on_interface_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::valueTask]
+            interface I {}
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsValueTask::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn builder_attribute_on_struct_with_builder_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::builder]
+            struct S {
+                builder: string
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsBuilder::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn intern_attribute_on_non_string_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::intern]
+                i: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsIntern::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn custom_default_attribute_with_defaults_for_reference_fields_succeeds() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::customDefault(\"name = string.Empty\", \"count = 1\")]
+            struct S {
+                name: string
+                count: int32
+                tags: Sequence<string>?
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test_case("[cs::customDefault]"; "no default")]
+    #[test_case("[cs::customDefault(\"count = 1\")]"; "default for value field only")]
+    fn custom_default_attribute_without_default_for_reference_field_fails(attribute: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            {attribute}
+            struct S {{
+                name: string
+                count: int32
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsCustomDefault::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("[cs::customDefault(\"unknown = 1\")]", "unknown"; "unknown field")]
+    #[test_case("[cs::customDefault(\"count = 1\", \"count = 2\")]", "count"; "duplicate field")]
+    #[test_case("[cs::customDefault(\"count\")]", "count"; "missing expression")]
+    fn custom_default_attribute_with_invalid_argument_fails(attribute: &str, argument: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            {attribute}
+            struct S {{
+                count: int32
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ArgumentNotSupported {
+            argument: argument.to_owned(),
+            directive: CsCustomDefault::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("a: string"; "no numeric field")]
+    #[test_case("a: int32\nb: float64"; "two numeric fields")]
+    #[test_case("a: int32?"; "optional numeric field")]
+    fn formattable_attribute_without_exactly_one_numeric_field_fails(fields: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::formattable]
+            struct S {{
+                {fields}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsFormattable::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("a: string"; "non numeric field")]
+    #[test_case("a: int32\nb: string"; "other fields")]
+    #[test_case("a: int32?"; "optional numeric field")]
+    fn numeric_attribute_without_a_single_numeric_field_fails(fields: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::numeric]
+            struct S {{
+                {fields}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsNumeric::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn encode_order_attribute_with_complete_permutation_parses() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::encodeOrder(1)] a: int32
+                [cs::encodeOrder(0)] b: string
+                tag(1) c: int32?
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test_case("[cs::encodeOrder(0)] a: int32\n[cs::encodeOrder(0)] b: int32", "0"; "duplicate order")]
+    #[test_case("[cs::encodeOrder(0)] a: int32\n[cs::encodeOrder(2)] b: int32", "2"; "gap")]
+    #[test_case("[cs::encodeOrder(first)] a: int32", "first"; "not an integer")]
+    fn encode_order_attribute_with_invalid_order_fails(fields: &str, argument: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            struct S {{
+                {fields}
+            }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(&slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ArgumentNotSupported {
+            argument: argument.to_owned(),
+            directive: CsEncodeOrder::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn encode_order_attribute_on_some_fields_only_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::encodeOrder(0)] a: int32
+                b: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::MissingRequiredAttribute {
+            attribute: CsEncodeOrder::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn encode_order_attribute_on_tagged_field_fails() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct S {
+                [cs::encodeOrder(0)] tag(1) a: int32?
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsEncodeOrder::directive().to_owned(),
+        });
+
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn readonly_struct_with_mutable_collection_fields_warns() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::readonly]
+            struct S {
+                a: Sequence<int32>
+                b: Dictionary<string, int32>
+                [cs::readonlyList] c: Sequence<int32>
+                d: [cs::frozen] Dictionary<string, int32>
+                e: int32
+            }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.level() == DiagnosticLevel::Warning && diagnostic.span().is_some()));
+        let expected = ["a", "b"].map(|field| {
+            Diagnostic::new(Lint::IncorrectDocComment {
+                message: format!("field '{field}' of readonly struct 'S' is a mutable collection. Consider mapping it with 'cs::readonlyList' or to an immutable collection type."),
+            })
+        });
+
+        check_diagnostics(diagnostics, expected);
+    }
+
+    #[test]
+    fn readonly_struct_with_mutable_collection_field_can_be_allowed() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::readonly]
+            struct S {
+                [allow(IncorrectDocComment)]
+                a: Sequence<int32>
+            }
+        ";
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn attributes_unsupported_by_target_framework_warn() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::required]
+            struct S {
+                a: int32
+                b: [cs::frozen] Dictionary<string, int32>?
+            }
+        ";
+        let options = CsOptions {
+            target_framework: TargetFramework::Net6,
+            ..CsOptions::default()
+        };
+        let mut compilation_state =
+            slicec::compile_from_strings(&[slice], Some(&options.slice_options), cs_patcher, cs_validator);
+
+        // Act
+        check_target_framework_support(&mut compilation_state, &options);
+
+        // Assert
+        let diagnostics = diagnostics_from_compilation_state(compilation_state, &options.slice_options);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.level() == DiagnosticLevel::Warning && diagnostic.span().is_some()));
+        let expected = [("a", "cs::required"), ("b", "cs::frozen")].map(|(field, attribute)| {
+            Diagnostic::new(Lint::IncorrectDocComment {
+                message: format!("field '{field}' of struct 'S' is marked with '{attribute}', which isn't supported by the target framework."),
+            })
+        });
+
+        check_diagnostics(diagnostics, expected);
+    }
 }
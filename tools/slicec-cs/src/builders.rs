@@ -4,7 +4,6 @@ use crate::code_block::CodeBlock;
 use crate::code_gen_util::TypeContext;
 use crate::comments::CommentTag;
 use crate::cs_attributes::CsType;
-use crate::cs_util::*;
 use crate::member_util::escape_parameter_name;
 use crate::slicec_ext::*;
 use slicec::grammar::*;
@@ -35,6 +34,32 @@ pub trait AttributeBuilder {
         self
     }
 
+    /// Adds the C# GeneratedCode attribute, which identifies the code as generated by this version of slicec-cs.
+    fn add_generated_code_attribute(&mut self) -> &mut Self {
+        self.add_attribute(format!(
+            r#"global::System.CodeDom.Compiler.GeneratedCode("slicec-cs", "{}")"#,
+            env!("CARGO_PKG_VERSION"),
+        ))
+    }
+
+    /// Adds the C# MethodImpl attribute, which asks the JIT compiler to inline the method wherever possible.
+    fn add_aggressive_inlining_attribute(&mut self) -> &mut Self {
+        self.add_attribute(
+            "global::System.Runtime.CompilerServices.MethodImpl(global::System.Runtime.CompilerServices.MethodImplOptions.AggressiveInlining)",
+        )
+    }
+
+    /// Adds the C# SkipLocalsInit attribute, which stops the runtime from zero-initializing the method's locals. The C#
+    /// compiler only accepts this attribute in projects that allow unsafe code.
+    fn add_skip_locals_init_attribute(&mut self) -> &mut Self {
+        self.add_attribute("global::System.Runtime.CompilerServices.SkipLocalsInit")
+    }
+
+    /// Adds the C# CompilerGenerated attribute, which code coverage tools use to exclude a member from coverage.
+    fn add_compiler_generated_attribute(&mut self) -> &mut Self {
+        self.add_attribute("global::System.Runtime.CompilerServices.CompilerGenerated")
+    }
+
     /// Adds the C# Obsolete attribute if the entity has the Slice deprecated attribute.
     fn add_obsolete_attribute(&mut self, entity: &dyn Entity) -> &mut Self {
         if let Some(attribute) = entity.obsolete_attribute() {
@@ -48,15 +73,16 @@ pub trait CommentBuilder {
     fn add_comment(&mut self, tag: &str, content: impl Into<String>) -> &mut Self;
 
     fn add_generated_remark(&mut self, generated_type: &str, slice_type: &impl Entity) -> &mut Self {
-        self.add_comment(
-            "remarks",
-            format!(
-                "The Slice compiler generated this {} from the Slice {} <c>{}</c>.",
-                generated_type,
-                slice_type.kind(),
-                slice_type.module_scoped_identifier(),
-            ),
+        let mut remarks = format!(
+            "The Slice compiler generated this {} from the Slice {} <c>{}</c>.",
+            generated_type,
+            slice_type.kind(),
+            slice_type.module_scoped_identifier(),
         );
+        if let Some(user_remarks) = slice_type.formatted_doc_comment_remarks() {
+            remarks = remarks + "\n" + &user_remarks;
+        }
+        self.add_comment("remarks", remarks);
         self
     }
 
@@ -66,16 +92,17 @@ pub trait CommentBuilder {
         note: impl Into<String>,
         slice_type: &impl Entity,
     ) -> &mut Self {
-        self.add_comment(
-            "remarks",
-            format!(
-                "The Slice compiler generated this {} from Slice {} <c>{}</c>.\n{}",
-                generated_type,
-                slice_type.kind(),
-                slice_type.module_scoped_identifier(),
-                note.into(),
-            ),
+        let mut remarks = format!(
+            "The Slice compiler generated this {} from Slice {} <c>{}</c>.\n{}",
+            generated_type,
+            slice_type.kind(),
+            slice_type.module_scoped_identifier(),
+            note.into(),
         );
+        if let Some(user_remarks) = slice_type.formatted_doc_comment_remarks() {
+            remarks = remarks + "\n" + &user_remarks;
+        }
+        self.add_comment("remarks", remarks);
         self
     }
 
@@ -88,6 +115,22 @@ pub trait CommentBuilder {
     ) -> &mut Self;
 
     fn add_comments(&mut self, comments: Vec<CommentTag>) -> &mut Self;
+
+    /// Adds a `typeparam` comment for the provided type parameter. Type parameter comments are always placed after the
+    /// summary and any previously added type parameter comments, regardless of when they're added.
+    fn add_type_param_comment(&mut self, name: &str, description: impl Into<String>) -> &mut Self;
+}
+
+/// Inserts a `typeparam` comment tag after the leading `summary` and `typeparam` tags of the provided comments.
+fn insert_type_param_comment(comments: &mut Vec<CommentTag>, name: &str, description: String) {
+    let position = comments
+        .iter()
+        .take_while(|comment| comment.tag == "summary" || comment.tag == "typeparam")
+        .count();
+    comments.insert(
+        position,
+        CommentTag::with_tag_attribute("typeparam", "name", name, description),
+    );
 }
 
 #[derive(Clone, Debug)]
@@ -226,6 +269,11 @@ impl CommentBuilder for ContainerBuilder {
         self.comments.extend(comments);
         self
     }
+
+    fn add_type_param_comment(&mut self, name: &str, description: impl Into<String>) -> &mut Self {
+        insert_type_param_comment(&mut self.comments, name, description.into());
+        self
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -243,6 +291,7 @@ pub struct FunctionBuilder {
     parameters: Vec<String>,
     body: CodeBlock,
     base_arguments: Vec<String>,
+    this_arguments: Vec<String>,
     comments: Vec<CommentTag>,
     attributes: Vec<String>,
     function_type: FunctionType,
@@ -260,6 +309,7 @@ impl FunctionBuilder {
             comments: Vec::new(),
             attributes: Vec::new(),
             base_arguments: Vec::new(),
+            this_arguments: Vec::new(),
             function_type,
             inherit_doc: false,
         }
@@ -271,9 +321,25 @@ impl FunctionBuilder {
         param_name: &str,
         default_value: Option<&str>,
         doc_comment: Option<String>,
+    ) -> &mut Self {
+        self.add_parameter_with_attributes(param_type, param_name, default_value, doc_comment, &[])
+    }
+
+    /// Adds a parameter preceded by the provided C# attributes, for example: `[FromBody] string name`.
+    pub fn add_parameter_with_attributes(
+        &mut self,
+        param_type: &str,
+        param_name: &str,
+        default_value: Option<&str>,
+        doc_comment: Option<String>,
+        attributes: &[String],
     ) -> &mut Self {
         self.parameters.push(format!(
-            "{param_type} {param_name}{default_value}",
+            "{attributes}{param_type} {param_name}{default_value}",
+            attributes = attributes
+                .iter()
+                .map(|attribute| format!("[{attribute}] "))
+                .collect::<String>(),
             default_value = match default_value {
                 Some(value) => format!(" = {value}"),
                 None => "".to_string(),
@@ -294,6 +360,15 @@ impl FunctionBuilder {
         self
     }
 
+    /// Chains this constructor to another constructor of the same type, which is called with the provided arguments.
+    /// Each argument is written on its own line, since arguments are often long decoding expressions.
+    pub fn add_this_arguments(&mut self, arguments: &[String]) -> &mut Self {
+        for arg in arguments {
+            self.this_arguments.push(arg.to_owned());
+        }
+        self
+    }
+
     pub fn set_body(&mut self, body: CodeBlock) -> &mut Self {
         self.body = body;
         self
@@ -304,6 +379,23 @@ impl FunctionBuilder {
         self
     }
 
+    /// Returns true if the body of this function is short enough to benefit from aggressive inlining.
+    pub fn has_trivial_body(&self) -> bool {
+        const MAX_TRIVIAL_BODY_LINES: usize = 4;
+        !self.body.is_empty() && self.body.to_string().lines().count() <= MAX_TRIVIAL_BODY_LINES
+    }
+
+    /// Returns true if the locals of this function don't need to be zero-initialized. C# requires locals to be assigned
+    /// before they're read, so only the memory allocated with `stackalloc` could be read without being initialized.
+    pub fn can_skip_locals_init(&self) -> bool {
+        !self.body.to_string().contains("stackalloc")
+    }
+
+    pub fn add_sets_required_members_attribute(&mut self) -> &mut Self {
+        self.add_attribute("global::System.Diagnostics.CodeAnalysis.SetsRequiredMembers");
+        self
+    }
+
     pub fn add_never_editor_browsable_attribute(&mut self) -> &mut Self {
         self.add_attribute(
             "global::System.ComponentModel.EditorBrowsable(global::System.ComponentModel.EditorBrowsableState.Never)",
@@ -349,11 +441,12 @@ impl FunctionBuilder {
                     None
                 };
 
-            self.add_parameter(
+            self.add_parameter_with_attributes(
                 &parameter_type,
                 &parameter_name,
                 default_value,
                 parameter.formatted_param_doc_comment(),
+                &parameter.cs_attributes(),
             );
         }
 
@@ -388,49 +481,14 @@ impl FunctionBuilder {
             Some("A cancellation token that receives the cancellation requests.".to_owned()),
         );
 
-        if let Some(comment) = operation.comment() {
-            // Generate documentation for any '@returns' tags on the operation.
-            match comment.returns.as_slice() {
-                // Do nothing if there's no return tags.
-                [] => {}
-
-                // If there's a single return tag, generate a normal `returns` message.
-                [single] => {
-                    let message = format_comment_message(&single.message, &operation.namespace());
-                    self.add_comment("returns", message);
-                }
-
-                // If there's multiple return tags, this function returns a tuple.
-                // We generate a returns message that lists the elements of the tuple as a bulleted list.
-                multiple => {
-                    let mut content = "A tuple containing:\n<list type=\"bullet\">\n".to_owned();
-                    for return_tag in multiple {
-                        // TODO add references to the types/identifiers here later!
-                        let message = format_comment_message(&return_tag.message, &operation.namespace());
-                        content = content + "<item><description>" + message.trim_end() + "</description></item>\n";
-                    }
-                    content += "</list>\n";
-
-                    self.add_comment("returns", content);
-                }
-            }
-
-            // Generate documentation for any '@throws' tags on the operation.
-            for throws_tag in &comment.throws {
-                let message = format_comment_message(&throws_tag.message, &operation.namespace());
-                match throws_tag.thrown_type() {
-                    Ok(exception) => {
-                        let exception_name = exception.escape_scoped_identifier(&operation.namespace());
-                        self.add_comment_with_attribute("exception", "cref", &exception_name, message);
-                    }
-                    Err(identifier) => {
-                        // If there was an error resolving the link, print the identifier without any formatting.
-                        self.add_comment_with_attribute("exception", "cref", &identifier.value, message);
-                    }
-                }
-            }
+        // Generate documentation for any '@returns' tags on the operation.
+        if let Some(returns_tag) = operation.formatted_doc_comment_returns() {
+            self.comments.push(returns_tag);
         }
 
+        // Generate documentation for any '@throws' tags on the operation.
+        self.comments.extend(operation.formatted_doc_comment_exceptions());
+
         // Generate documentation for "void" returns. This is only done for void operations
         // since they can't have '@returns' tags in their doc comments.
         if operation.return_type.is_empty() {
@@ -483,7 +541,23 @@ impl Builder for FunctionBuilder {
             _ => write!(code, "\n    : base({})", self.base_arguments.join(", ")),
         }
 
-        match self.function_type {
+        match self.this_arguments.as_slice() {
+            [] => {}
+            _ => write!(
+                code,
+                "\n    : this(\n        {})",
+                CodeBlock::from(self.this_arguments.join(",\n")).indent().indent(),
+            ),
+        }
+
+        // Expression bodies can only hold a single expression. If the body holds statements instead (which are always
+        // terminated by a semicolon), we fall back to generating a block body.
+        let function_type = match self.function_type {
+            FunctionType::ExpressionBody if self.body.to_string().ends_with(';') => FunctionType::BlockBody,
+            function_type => function_type,
+        };
+
+        match function_type {
             FunctionType::Declaration => {
                 code.writeln(";");
             }
@@ -616,4 +690,81 @@ impl CommentBuilder for FunctionBuilder {
         self.comments.extend(comments);
         self
     }
+
+    fn add_type_param_comment(&mut self, name: &str, description: impl Into<String>) -> &mut Self {
+        insert_type_param_comment(&mut self.comments, name, description.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expression_body_with_statements_falls_back_to_block_body() {
+        // Arrange
+        let mut builder = FunctionBuilder::new("public", "int", "Foo", FunctionType::ExpressionBody);
+        builder.set_body("var x = Bar();\nreturn x;".into());
+
+        // Act
+        let code = builder.build().to_string();
+
+        // Assert
+        let expected = "\
+public int Foo()
+{
+    var x = Bar();
+    return x;
+}";
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn expression_body_with_expression() {
+        // Arrange
+        let mut builder = FunctionBuilder::new("public", "int", "Foo", FunctionType::ExpressionBody);
+        builder.set_body("Bar()".into());
+
+        // Act
+        let code = builder.build().to_string();
+
+        // Assert
+        assert_eq!(code, "public int Foo() =>\n    Bar();");
+    }
+
+    #[test]
+    fn parameter_attributes_precede_the_parameter_type() {
+        // Arrange
+        let mut builder = FunctionBuilder::new("public", "void", "Foo", FunctionType::Declaration);
+        builder.add_parameter_with_attributes("string", "name", None, None, &["FromBody".to_owned()]);
+
+        // Act
+        let code = builder.build().to_string();
+
+        // Assert
+        assert_eq!(code, "public void Foo([FromBody] string name);");
+    }
+
+    #[test]
+    fn type_param_comments_follow_summary() {
+        // Arrange
+        let mut builder = ContainerBuilder::new("public sealed class", "Foo<T, U>");
+        builder
+            .add_comment("summary", "A generic class.")
+            .add_comment("remarks", "Some remarks.")
+            .add_type_param_comment("T", "The first type.")
+            .add_type_param_comment("U", "The second type.");
+
+        // Act
+        let code = builder.build().to_string();
+
+        // Assert
+        let expected = r#"/// <summary>A generic class.</summary>
+/// <typeparam name="T">The first type.</typeparam>
+/// <typeparam name="U">The second type.</typeparam>
+/// <remarks>Some remarks.</remarks>
+public sealed class Foo<T, U>"#;
+        assert!(code.starts_with(expected));
+    }
 }
@@ -14,13 +14,28 @@ impl CsAttribute {
         check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
 
         let attribute = args.first().cloned().unwrap_or_default();
+        if let Some(note) = check_attribute_string(&attribute, args.is_empty()) {
+            Diagnostic::new(Error::ArgumentNotSupported {
+                argument: attribute.clone(),
+                directive: Self::directive().to_owned(),
+            })
+            .set_span(span)
+            .add_note(note, None)
+            .push_into(diagnostics);
+        }
+
         CsAttribute { attribute }
     }
 
     pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
         if !matches!(
             applied_on,
-            Attributables::Enum(_) | Attributables::Enumerator(_) | Attributables::Field(_),
+            Attributables::Struct(_)
+                | Attributables::Enum(_)
+                | Attributables::Enumerator(_)
+                | Attributables::Field(_)
+                | Attributables::Operation(_)
+                | Attributables::Parameter(_),
         ) {
             // TODO Add a note explaining what this can be applied to, and how to put attributes on other things.
             report_unexpected_attribute(self, span, None, diagnostics);
@@ -28,4 +43,18 @@ impl CsAttribute {
     }
 }
 
+/// Checks that the provided string can be emitted verbatim as a C# attribute, returning a note explaining the problem
+/// if it can't. Missing arguments are already reported by `check_that_exactly_one_argument_was_provided`.
+fn check_attribute_string(attribute: &str, is_missing: bool) -> Option<&'static str> {
+    if is_missing {
+        None
+    } else if attribute.trim().is_empty() {
+        Some("The C# attribute cannot be empty.")
+    } else if attribute.contains('\n') {
+        Some("The C# attribute cannot contain newlines.")
+    } else {
+        None
+    }
+}
+
 implement_attribute_kind_for!(CsAttribute, "cs::attribute", true);
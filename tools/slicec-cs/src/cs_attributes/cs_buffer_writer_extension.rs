@@ -0,0 +1,24 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+#[derive(Debug)]
+pub struct CsBufferWriterExtension {}
+
+impl CsBufferWriterExtension {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsBufferWriterExtension {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if !matches!(applied_on, Attributables::Struct(_)) {
+            report_unexpected_attribute(self, span, None, diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsBufferWriterExtension, "cs::bufferWriterExtension", false);
@@ -0,0 +1,47 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::EntityExt;
+use convert_case::Case;
+use slicec::grammar::{NamedSymbol, Symbol};
+
+#[derive(Debug)]
+pub struct CsBuilder {}
+
+impl CsBuilder {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsBuilder {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Struct(struct_def) => {
+                // The nested builder class would conflict with a property of the same name.
+                for field in struct_def.fields() {
+                    if field.cs_identifier(Case::Pascal) == "Builder" {
+                        Diagnostic::new(Error::UnexpectedAttribute {
+                            attribute: Self::directive().to_owned(),
+                        })
+                        .set_span(span)
+                        .add_note(
+                            format!(
+                                "The '{}' attribute generates a nested 'Builder' class, which conflicts with field '{}'.",
+                                Self::directive(),
+                                field.identifier(),
+                            ),
+                            Some(field.span()),
+                        )
+                        .push_into(diagnostics);
+                    }
+                }
+            }
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsBuilder, "cs::builder", false);
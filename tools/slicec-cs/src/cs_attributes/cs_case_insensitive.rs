@@ -0,0 +1,31 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::EnumExt;
+
+/// Makes the generated `TryParse` method of an enum match enumerator names case-insensitively.
+#[derive(Debug)]
+pub struct CsCaseInsensitive {}
+
+impl CsCaseInsensitive {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsCaseInsensitive {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Enum(enum_def) if enum_def.is_mapped_to_cs_enum() => {}
+            Attributables::Enum(_) => {
+                let note = "Enums with associated fields are not mapped to C# enums, so they cannot be parsed";
+                report_unexpected_attribute(self, span, Some(note), diagnostics);
+            }
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsCaseInsensitive, "cs::caseInsensitive", false);
@@ -0,0 +1,44 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::TypeRefExt;
+use slicec::grammar::{Member, NamedSymbol, Symbol};
+
+#[derive(Debug)]
+pub struct CsComparable {}
+
+impl CsComparable {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsComparable {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if let Attributables::Struct(struct_def) = applied_on {
+            for field in struct_def.fields() {
+                if !field.data_type().is_comparable() {
+                    Diagnostic::new(Error::UnexpectedAttribute {
+                        attribute: Self::directive().to_owned(),
+                    })
+                    .set_span(span)
+                    .add_note(
+                        format!(
+                            "The '{}' attribute can only be applied to structs with comparable fields, but field '{}' is not comparable.",
+                            Self::directive(),
+                            field.identifier(),
+                        ),
+                        Some(field.span()),
+                    )
+                    .push_into(diagnostics);
+                }
+            }
+        } else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsComparable, "cs::comparable", false);
@@ -0,0 +1,114 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::{EntityExt, TypeRefExt};
+use convert_case::Case;
+use slicec::grammar::{Member, NamedSymbol, Symbol};
+
+#[derive(Debug)]
+pub struct CsCustomDefault {
+    /// The default value of each field that was given one, as pairs of field identifiers and C# expressions.
+    pub field_defaults: Vec<(String, String)>,
+}
+
+impl CsCustomDefault {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        // Each argument has the form `<field> = <C# expression>`.
+        let mut field_defaults = Vec::new();
+        for arg in args {
+            match arg.split_once('=') {
+                Some((field, value)) if !field.trim().is_empty() && !value.trim().is_empty() => {
+                    field_defaults.push((field.trim().to_owned(), value.trim().to_owned()));
+                }
+                _ => Diagnostic::new(Error::ArgumentNotSupported {
+                    argument: arg.clone(),
+                    directive: Self::directive().to_owned(),
+                })
+                .set_span(span)
+                .add_note("Default values must be provided as '<field> = <C# expression>'.", None)
+                .push_into(diagnostics),
+            }
+        }
+
+        CsCustomDefault { field_defaults }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::Struct(struct_def) = applied_on else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+            return;
+        };
+        let fields = struct_def.fields();
+
+        for (i, (identifier, _)) in self.field_defaults.iter().enumerate() {
+            let note = if !fields.iter().any(|field| field.identifier() == identifier) {
+                format!(
+                    "Struct '{}' has no field named '{identifier}'.",
+                    struct_def.identifier()
+                )
+            } else if self.field_defaults[..i].iter().any(|(other, _)| other == identifier) {
+                format!("A default value was already provided for field '{identifier}'.")
+            } else {
+                continue;
+            };
+            Diagnostic::new(Error::ArgumentNotSupported {
+                argument: identifier.clone(),
+                directive: Self::directive().to_owned(),
+            })
+            .set_span(span)
+            .add_note(note, None)
+            .push_into(diagnostics);
+        }
+
+        for field in &fields {
+            // The generated static field would conflict with a property of the same name.
+            if field.cs_identifier(Case::Pascal) == "Default" {
+                Diagnostic::new(Error::UnexpectedAttribute {
+                    attribute: Self::directive().to_owned(),
+                })
+                .set_span(span)
+                .add_note(
+                    format!(
+                        "The '{}' attribute generates a static 'Default' field, which conflicts with field '{}'.",
+                        Self::directive(),
+                        field.identifier(),
+                    ),
+                    Some(field.span()),
+                )
+                .push_into(diagnostics);
+            }
+
+            // Value-typed and optional fields can safely default to `default`, but non-optional references can't.
+            let data_type = field.data_type();
+            if !data_type.is_optional
+                && !data_type.is_value_type()
+                && self.default_value_of(field.identifier()).is_none()
+            {
+                Diagnostic::new(Error::UnexpectedAttribute {
+                    attribute: Self::directive().to_owned(),
+                })
+                .set_span(span)
+                .add_note(
+                    format!(
+                        "No default value was provided for field '{}', which is a non-optional reference type.",
+                        field.identifier(),
+                    ),
+                    Some(field.span()),
+                )
+                .push_into(diagnostics);
+            }
+        }
+    }
+
+    /// Returns the default value provided for the field with the given identifier, if any.
+    pub fn default_value_of(&self, identifier: &str) -> Option<&str> {
+        self.field_defaults
+            .iter()
+            .find(|(field, _)| field == identifier)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+implement_attribute_kind_for!(CsCustomDefault, "cs::customDefault", false);
@@ -0,0 +1,26 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+/// Structs marked with `cs::dataContract` can also be marked with `cs::json`: `System.Text.Json` ignores the
+/// `DataContract` and `DataMember` attributes, so each serializer keeps using its own mapping.
+#[derive(Debug)]
+pub struct CsDataContract {}
+
+impl CsDataContract {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsDataContract {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if !matches!(applied_on, Attributables::Struct(_)) {
+            report_unexpected_attribute(self, span, None, diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsDataContract, "cs::dataContract", false);
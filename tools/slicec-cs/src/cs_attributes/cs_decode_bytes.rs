@@ -0,0 +1,38 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+#[derive(Debug)]
+pub struct CsDecodeBytes {
+    pub method: String,
+}
+
+impl CsDecodeBytes {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let method = args.first().cloned().unwrap_or_default();
+        if !args.is_empty() && method.trim().is_empty() {
+            Diagnostic::new(Error::ArgumentNotSupported {
+                argument: method.clone(),
+                directive: Self::directive().to_owned(),
+            })
+            .set_span(span)
+            .add_note("The name of the converter method cannot be empty.", None)
+            .push_into(diagnostics);
+        }
+
+        CsDecodeBytes { method }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if !matches!(applied_on, Attributables::CustomType(_)) {
+            let note = format!("'{}' can only be applied to custom types", Self::directive());
+            report_unexpected_attribute(self, span, Some(&note), diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsDecodeBytes, "cs::decodeBytes", false);
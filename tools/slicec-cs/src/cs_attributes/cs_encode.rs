@@ -0,0 +1,37 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::AttributeFunctions;
+
+#[derive(Debug)]
+pub struct CsEncode {
+    pub method: String,
+}
+
+impl CsEncode {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let method = args.first().cloned().unwrap_or_default();
+        CsEncode { method }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Field(field) if field.has_attribute::<CsType>() => {}
+            Attributables::Field(_) => {
+                let note = format!(
+                    "'{}' can only be applied to fields that are mapped to a custom C# type with '{}'",
+                    Self::directive(),
+                    CsType::directive(),
+                );
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+            }
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsEncode, "cs::encode", false);
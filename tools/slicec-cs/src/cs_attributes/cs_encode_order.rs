@@ -0,0 +1,66 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::{AttributeFunctions, Contained, Entities, Entity, Field, Member, Struct};
+
+#[derive(Debug)]
+pub struct CsEncodeOrder {
+    pub order: usize,
+}
+
+impl CsEncodeOrder {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let argument = args.first().cloned().unwrap_or_default();
+        let order = match argument.parse::<usize>() {
+            Ok(order) => order,
+            Err(_) => {
+                if !argument.is_empty() {
+                    Diagnostic::new(Error::ArgumentNotSupported {
+                        argument,
+                        directive: Self::directive().to_owned(),
+                    })
+                    .set_span(span)
+                    .add_note("The argument must be a non-negative integer.", None)
+                    .push_into(diagnostics);
+                }
+                0
+            }
+        };
+
+        CsEncodeOrder { order }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::Field(field) = applied_on else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+            return;
+        };
+
+        let note = if !matches!(field.parent().concrete_entity(), Entities::Struct(_)) {
+            "the cs::encodeOrder attribute can only be applied to the fields of structs"
+        } else if field.is_tagged() {
+            "the cs::encodeOrder attribute cannot be applied to tagged fields, which are always encoded by tag"
+        } else {
+            return;
+        };
+        report_unexpected_attribute(self, span, Some(note), diagnostics);
+    }
+
+    /// Returns the fields of a struct in the order they're encoded and decoded. Fields marked with `cs::encodeOrder`
+    /// are sorted by their order, while tagged fields are always encoded last. Fields are left in their declaration
+    /// order if none of them are marked with `cs::encodeOrder`.
+    pub fn encoded_fields(struct_def: &Struct) -> Vec<&Field> {
+        let mut fields = struct_def.fields();
+        fields.sort_by_key(|field| {
+            let order = field.find_attribute::<CsEncodeOrder>().map(|attribute| attribute.order);
+            (field.is_tagged(), order)
+        });
+        fields
+    }
+}
+
+implement_attribute_kind_for!(CsEncodeOrder, "cs::encodeOrder", false);
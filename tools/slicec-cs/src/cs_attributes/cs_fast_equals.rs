@@ -0,0 +1,33 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::AttributeFunctions;
+
+#[derive(Debug)]
+pub struct CsFastEquals {}
+
+impl CsFastEquals {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsFastEquals {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Struct(struct_def) if struct_def.has_attribute::<CsClass>() => {
+                let note = format!(
+                    "Structs marked with '{}' are generated as classes, which are never copied when compared",
+                    CsClass::directive(),
+                );
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+            }
+            Attributables::Struct(_) => {}
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsFastEquals, "cs::fastEquals", false);
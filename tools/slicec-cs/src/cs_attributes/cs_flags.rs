@@ -0,0 +1,57 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::EnumExt;
+use slicec::grammar::{NamedSymbol, Symbol};
+
+#[derive(Debug)]
+pub struct CsFlags {}
+
+impl CsFlags {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsFlags {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Enum(enum_def) if enum_def.is_mapped_to_cs_enum() => {
+                // Each enumerator of a flags enum must be a single bit (or zero, for representing no flags).
+                let invalid_enumerators = enum_def
+                    .enumerators()
+                    .into_iter()
+                    .filter(|enumerator| enumerator.value() < 0 || enumerator.value().count_ones() > 1)
+                    .collect::<Vec<_>>();
+
+                if !invalid_enumerators.is_empty() {
+                    let mut diagnostic = Diagnostic::new(Error::UnexpectedAttribute {
+                        attribute: Self::directive().to_owned(),
+                    })
+                    .set_span(span);
+                    for enumerator in invalid_enumerators {
+                        diagnostic = diagnostic.add_note(
+                            format!(
+                                "The values of enumerators in enums marked with '{}' must be zero or a power of two, but enumerator '{}' has the value '{}'.",
+                                Self::directive(),
+                                enumerator.identifier(),
+                                enumerator.value(),
+                            ),
+                            Some(enumerator.span()),
+                        );
+                    }
+                    diagnostic.push_into(diagnostics);
+                }
+            }
+            Attributables::Enum(_) => {
+                let note = "Enums with associated fields are not mapped to C# enums, so they cannot be flags";
+                report_unexpected_attribute(self, span, Some(note), diagnostics);
+            }
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsFlags, "cs::flags", false);
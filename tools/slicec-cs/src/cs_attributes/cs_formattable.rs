@@ -0,0 +1,52 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::PrimitiveExt;
+use slicec::grammar::{AttributeFunctions, Field, Member, Struct, Types};
+
+#[derive(Debug)]
+pub struct CsFormattable {}
+
+impl CsFormattable {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsFormattable {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::Struct(struct_def) = applied_on else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+            return;
+        };
+
+        if Self::formattable_field(struct_def).is_none() {
+            let note = format!(
+                "The '{}' attribute can only be applied to structs with exactly one non-optional numeric field.",
+                Self::directive(),
+            );
+            report_unexpected_attribute(self, span, Some(&note), diagnostics);
+        }
+    }
+
+    /// Returns the field that the generated `ToString(string?, IFormatProvider?)` and `TryFormat` methods forward to.
+    /// This is the struct's only non-optional numeric field, or `None` if the struct has no such field, or more than
+    /// one.
+    pub fn formattable_field(struct_def: &Struct) -> Option<&Field> {
+        let mut numeric_fields = struct_def.fields().into_iter().filter(|field| {
+            let data_type = field.data_type();
+            !data_type.is_optional
+                && !field.has_attribute::<CsType>()
+                && matches!(data_type.concrete_type(), Types::Primitive(primitive) if primitive.is_cs_numeric())
+        });
+
+        match (numeric_fields.next(), numeric_fields.next()) {
+            (Some(field), None) => Some(field),
+            _ => None,
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsFormattable, "cs::formattable", false);
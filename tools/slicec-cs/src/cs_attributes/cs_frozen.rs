@@ -0,0 +1,36 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::{AttributeFunctions, Types};
+
+#[derive(Debug)]
+pub struct CsFrozen {}
+
+impl CsFrozen {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsFrozen {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::TypeRef(type_ref) = applied_on else {
+            let note = "the cs::frozen attribute can only be applied to dictionaries";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+            return;
+        };
+
+        let note = match type_ref.concrete_type() {
+            Types::Dictionary(_) if type_ref.has_attribute::<CsType>() => {
+                "the cs::frozen attribute cannot be applied to dictionaries that are marked with cs::type"
+            }
+            Types::Dictionary(_) => return,
+            _ => "the cs::frozen attribute can only be applied to dictionaries",
+        };
+        report_unexpected_attribute(self, span, Some(note), diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(CsFrozen, "cs::frozen", false);
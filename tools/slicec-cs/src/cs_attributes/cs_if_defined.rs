@@ -0,0 +1,35 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+#[derive(Debug)]
+pub struct CsIfDefined {
+    pub symbol: String,
+}
+
+impl CsIfDefined {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let symbol = args.first().cloned().unwrap_or_default();
+        CsIfDefined { symbol }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if !matches!(
+            applied_on,
+            Attributables::Module(_)
+                | Attributables::Struct(_)
+                | Attributables::Class(_)
+                | Attributables::Exception(_)
+                | Attributables::Interface(_)
+                | Attributables::Enum(_)
+        ) {
+            report_unexpected_attribute(self, span, None, diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsIfDefined, "cs::ifDefined", false);
@@ -0,0 +1,55 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::{AttributeFunctions, Member, Primitive, Types};
+
+#[derive(Debug)]
+pub struct CsIntern {
+    /// The C# expression of the `StringPool` used to intern the decoded strings, if one was provided.
+    /// Otherwise, the strings are interned with `string.Intern`.
+    pub pool: Option<String>,
+}
+
+impl CsIntern {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        // The pool argument is optional, but at most one can be provided.
+        if args.len() > 1 {
+            check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+        }
+
+        let pool = args.first().cloned();
+        CsIntern { pool }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Field(field) if field.has_attribute::<CsType>() => {
+                let note = format!(
+                    "'{}' cannot be applied to fields that are mapped to a custom C# type with '{}'",
+                    Self::directive(),
+                    CsType::directive(),
+                );
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+            }
+            Attributables::Field(field)
+                if matches!(field.data_type().concrete_type(), Types::Primitive(Primitive::String)) => {}
+            Attributables::Field(_) => {
+                let note = format!("'{}' can only be applied to string fields", Self::directive());
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+            }
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+
+    /// Returns the C# expression that interns the string returned by `value`.
+    pub fn intern_call(&self, value: &str) -> String {
+        match &self.pool {
+            Some(pool) => format!("{pool}.GetOrAdd({value})"),
+            None => format!("string.Intern({value})"),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsIntern, "cs::intern", false);
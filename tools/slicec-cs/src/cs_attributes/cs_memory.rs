@@ -0,0 +1,47 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::TypeRefExt;
+use slicec::grammar::{AttributeFunctions, Types};
+
+#[derive(Debug)]
+pub struct CsMemory {}
+
+impl CsMemory {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsMemory {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::TypeRef(type_ref) = applied_on else {
+            let note = "the cs::memory attribute can only be applied to sequences";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+            return;
+        };
+
+        let note = match type_ref.concrete_type() {
+            Types::Sequence(_) if type_ref.is_optional => {
+                "the cs::memory attribute cannot be applied to optional sequences"
+            }
+            Types::Sequence(_) if type_ref.has_attribute::<CsType>() => {
+                "the cs::memory attribute cannot be applied to sequences that are marked with cs::type"
+            }
+            Types::Sequence(sequence_def)
+                if sequence_def.has_fixed_size_primitive_elements() && sequence_def.element_type.is_blittable() =>
+            {
+                return;
+            }
+            Types::Sequence(_) => {
+                "the cs::memory attribute can only be applied to sequences of blittable fixed-size numeric types"
+            }
+            _ => "the cs::memory attribute can only be applied to sequences",
+        };
+        report_unexpected_attribute(self, span, Some(note), diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(CsMemory, "cs::memory", false);
@@ -0,0 +1,34 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::AttributeFunctions;
+
+#[derive(Debug)]
+pub struct CsMutable {}
+
+impl CsMutable {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsMutable {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Struct(struct_def) if struct_def.has_attribute::<CsReadonly>() => {
+                let note = format!(
+                    "'{}' cannot be applied to structs that are marked with '{}'",
+                    Self::directive(),
+                    CsReadonly::directive(),
+                );
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+            }
+            Attributables::Struct(_) => {}
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsMutable, "cs::mutable", false);
@@ -0,0 +1,50 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::PrimitiveExt;
+use slicec::grammar::{AttributeFunctions, Field, Member, Struct, Types};
+
+#[derive(Debug)]
+pub struct CsNumeric {}
+
+impl CsNumeric {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsNumeric {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::Struct(struct_def) = applied_on else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+            return;
+        };
+
+        if Self::numeric_field(struct_def).is_none() {
+            let note = format!(
+                "The '{}' attribute can only be applied to structs with a single non-optional numeric field.",
+                Self::directive(),
+            );
+            report_unexpected_attribute(self, span, Some(&note), diagnostics);
+        }
+    }
+
+    /// Returns the field that the generated arithmetic operators operate on. This is the struct's only field, or `None`
+    /// if the struct has other fields, or if this field isn't of a non-optional numeric type.
+    pub fn numeric_field(struct_def: &Struct) -> Option<&Field> {
+        let fields = struct_def.fields();
+        let [field] = fields[..] else {
+            return None;
+        };
+
+        let data_type = field.data_type();
+        let is_numeric = !data_type.is_optional
+            && !field.has_attribute::<CsType>()
+            && matches!(data_type.concrete_type(), Types::Primitive(primitive) if primitive.is_cs_numeric());
+        is_numeric.then_some(field)
+    }
+}
+
+implement_attribute_kind_for!(CsNumeric, "cs::numeric", false);
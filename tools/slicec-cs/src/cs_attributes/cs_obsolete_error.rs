@@ -0,0 +1,33 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+#[derive(Debug)]
+pub struct CsObsoleteError {}
+
+impl CsObsoleteError {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsObsoleteError {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if matches!(
+            applied_on,
+            Attributables::Module(_)
+                | Attributables::SliceFile(_)
+                | Attributables::Parameter(_)
+                | Attributables::TypeAlias(_)
+                | Attributables::TypeRef(_),
+        ) {
+            let note =
+                "This attribute can only be applied to elements that C#'s 'Obsolete' attribute can be applied to.";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsObsoleteError, "cs::obsoleteError", false);
@@ -0,0 +1,38 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use slicec::grammar::{AttributeFunctions, Contained, Entities, Entity, Member, Types};
+
+#[derive(Debug)]
+pub struct CsReadonlyList {}
+
+impl CsReadonlyList {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsReadonlyList {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        let Attributables::Field(field) = applied_on else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+            return;
+        };
+
+        let data_type = field.data_type();
+        let note = if !matches!(field.parent().concrete_entity(), Entities::Struct(_)) {
+            "the cs::readonlyList attribute can only be applied to the fields of structs"
+        } else if !matches!(data_type.concrete_type(), Types::Sequence(_)) || data_type.is_optional {
+            "the cs::readonlyList attribute can only be applied to fields with a non-optional sequence type"
+        } else if data_type.has_attribute::<CsType>() || data_type.has_attribute::<CsMemory>() {
+            "the cs::readonlyList attribute cannot be applied to sequences marked with cs::type or cs::memory"
+        } else {
+            return;
+        };
+        report_unexpected_attribute(self, span, Some(note), diagnostics);
+    }
+}
+
+implement_attribute_kind_for!(CsReadonlyList, "cs::readonlyList", false);
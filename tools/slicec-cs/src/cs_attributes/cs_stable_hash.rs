@@ -0,0 +1,53 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::TypeRefExt;
+use slicec::grammar::{AttributeFunctions, Member, NamedSymbol, Symbol};
+
+#[derive(Debug)]
+pub struct CsStableHash {}
+
+impl CsStableHash {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsStableHash {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Struct(struct_def) if struct_def.has_attribute::<CsValueEqualitySkipTagged>() => {
+                let note = format!(
+                    "'{}' cannot be applied to structs that are marked with '{}', since both generate a 'GetHashCode' method",
+                    Self::directive(),
+                    CsValueEqualitySkipTagged::directive(),
+                );
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+            }
+            Attributables::Struct(struct_def) => {
+                for field in struct_def.fields() {
+                    if !field.data_type().has_stable_hash_code() {
+                        Diagnostic::new(Error::UnexpectedAttribute {
+                            attribute: Self::directive().to_owned(),
+                        })
+                        .set_span(span)
+                        .add_note(
+                            format!(
+                                "The '{}' attribute can only be applied to structs whose fields have stable hash codes, but field '{}' doesn't have a stable hash code.",
+                                Self::directive(),
+                                field.identifier(),
+                            ),
+                            Some(field.span()),
+                        )
+                        .push_into(diagnostics);
+                    }
+                }
+            }
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsStableHash, "cs::stableHash", false);
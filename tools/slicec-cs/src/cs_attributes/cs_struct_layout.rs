@@ -0,0 +1,81 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+use crate::slicec_ext::TypeRefExt;
+use slicec::grammar::{Member, NamedSymbol, Symbol};
+
+#[derive(Debug)]
+pub struct CsStructLayout {
+    pub layout_kind: String,
+}
+
+impl CsStructLayout {
+    /// The layout kinds that can be passed to this attribute. These correspond to the enumerators of
+    /// `System.Runtime.InteropServices.LayoutKind`, except for `Explicit`, which requires a `FieldOffset` attribute on
+    /// each field. The generated structs store their fields in auto-properties, which can't have field offsets.
+    const LAYOUT_KINDS: [&'static str; 2] = ["Sequential", "Auto"];
+
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_exactly_one_argument_was_provided(args, Self::directive(), span, diagnostics);
+
+        let layout_kind = args.first().cloned().unwrap_or_default();
+        if !layout_kind.is_empty() && !Self::LAYOUT_KINDS.contains(&layout_kind.as_str()) {
+            let mut diagnostic = Diagnostic::new(Error::ArgumentNotSupported {
+                argument: layout_kind.clone(),
+                directive: Self::directive().to_owned(),
+            })
+            .set_span(span);
+            if layout_kind == "Explicit" {
+                diagnostic = diagnostic.add_note(
+                    "Explicit layouts require field offsets, which can't be applied to the generated properties.",
+                    None,
+                );
+            }
+            diagnostic
+                .add_note(
+                    format!("The valid arguments are: {}.", Self::LAYOUT_KINDS.join(", ")),
+                    None,
+                )
+                .push_into(diagnostics);
+        }
+
+        CsStructLayout { layout_kind }
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if let Attributables::Struct(struct_def) = applied_on {
+            if !struct_def.is_compact {
+                let note = format!(
+                    "The '{}' attribute can only be applied to compact structs.",
+                    Self::directive()
+                );
+                report_unexpected_attribute(self, span, Some(&note), diagnostics);
+                return;
+            }
+
+            for field in struct_def.fields() {
+                if !field.data_type().is_blittable() {
+                    Diagnostic::new(Error::UnexpectedAttribute {
+                        attribute: Self::directive().to_owned(),
+                    })
+                    .set_span(span)
+                    .add_note(
+                        format!(
+                            "The '{}' attribute can only be applied to structs with blittable fields, but field '{}' is not blittable.",
+                            Self::directive(),
+                            field.identifier(),
+                        ),
+                        Some(field.span()),
+                    )
+                    .push_into(diagnostics);
+                }
+            }
+        } else {
+            report_unexpected_attribute(self, span, None, diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsStructLayout, "cs::structLayout", false);
@@ -1,7 +1,7 @@
 // Copyright (c) ZeroC, Inc.
 
 use super::*;
-use slicec::grammar::Types;
+use slicec::grammar::{AttributeFunctions, Contained, CustomType, Entities, Entity, Field, Member, Types};
 
 #[derive(Debug)]
 pub struct CsType {
@@ -20,15 +20,89 @@ impl CsType {
 
     pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
         match applied_on {
-            Attributables::CustomType(_) => {}
+            Attributables::CustomType(custom_type) => self.validate_on_custom_type(custom_type, span, diagnostics),
             Attributables::TypeRef(type_ref)
                 if matches!(type_ref.concrete_type(), Types::Sequence(_) | Types::Dictionary(_)) => {}
+            Attributables::Field(field) => self.validate_on_field(field, span, diagnostics),
             _ => {
-                let note = "the cs::type attribute can only be applied to sequences, dictionaries, and custom types";
+                let note = "the cs::type attribute can only be applied to sequences, dictionaries, custom types, and struct fields";
                 report_unexpected_attribute(self, span, Some(note), diagnostics);
             }
         }
     }
+
+    /// Custom types whose wire form is a byte blob are converted to and from bytes with the user-provided methods
+    /// referenced by the `cs::encodeBytes` and `cs::decodeBytes` attributes. When either is present, both must be.
+    fn validate_on_custom_type(&self, custom_type: &CustomType, span: &Span, diagnostics: &mut Diagnostics) {
+        let converters = [
+            (custom_type.has_attribute::<CsEncodeBytes>(), CsEncodeBytes::directive()),
+            (custom_type.has_attribute::<CsDecodeBytes>(), CsDecodeBytes::directive()),
+        ];
+        if !converters.iter().any(|(has_converter, _)| *has_converter) {
+            return;
+        }
+
+        for (has_converter, converter) in converters {
+            if !has_converter {
+                Diagnostic::new(Error::MissingRequiredAttribute {
+                    attribute: converter.to_owned(),
+                })
+                .set_span(span)
+                .add_note(
+                    "Custom types encoded as bytes must reference the methods that convert them to and from bytes.",
+                    None,
+                )
+                .push_into(diagnostics);
+            }
+        }
+    }
+
+    /// Fields mapped to a custom C# type are encoded and decoded with the user-provided methods referenced by the
+    /// `cs::encode` and `cs::decode` attributes, which must both be present.
+    fn validate_on_field(&self, field: &Field, span: &Span, diagnostics: &mut Diagnostics) {
+        let Entities::Struct(struct_def) = field.parent().concrete_entity() else {
+            let note = "the cs::type attribute can only be applied to the fields of structs";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+            return;
+        };
+
+        if field.data_type().is_optional {
+            let note = "the cs::type attribute can only be applied to non-optional fields";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+        if struct_def.has_attribute::<CsEncodedSize>() || struct_def.has_attribute::<CsStableHash>() {
+            let note = format!(
+                "the cs::type attribute cannot be applied to fields of structs that are marked with '{}' or '{}'",
+                CsEncodedSize::directive(),
+                CsStableHash::directive(),
+            );
+            report_unexpected_attribute(self, span, Some(&note), diagnostics);
+        }
+        if field.has_attribute::<CsReadonlyList>() {
+            let note = "the cs::type attribute cannot be applied to fields that are marked with cs::readonlyList";
+            report_unexpected_attribute(self, span, Some(note), diagnostics);
+        }
+
+        for (has_converter, converter) in [
+            (field.has_attribute::<CsEncode>(), CsEncode::directive()),
+            (field.has_attribute::<CsDecode>(), CsDecode::directive()),
+        ] {
+            if !has_converter {
+                Diagnostic::new(Error::MissingRequiredAttribute {
+                    attribute: converter.to_owned(),
+                })
+                .set_span(span)
+                .add_note(
+                    format!(
+                        "Fields mapped to a custom C# type with '{}' must reference the methods that encode and decode it.",
+                        Self::directive(),
+                    ),
+                    None,
+                )
+                .push_into(diagnostics);
+            }
+        }
+    }
 }
 
 implement_attribute_kind_for!(CsType, "cs::type", false);
@@ -0,0 +1,29 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+#[derive(Debug)]
+pub struct CsValueEqualitySkipTagged {}
+
+impl CsValueEqualitySkipTagged {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsValueEqualitySkipTagged {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        match applied_on {
+            Attributables::Struct(struct_def) if struct_def.is_compact => {
+                let note = "Compact structs cannot have tagged fields, so their equality never skips any fields.";
+                report_unexpected_attribute(self, span, Some(note), diagnostics);
+            }
+            Attributables::Struct(_) => {}
+            _ => report_unexpected_attribute(self, span, None, diagnostics),
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsValueEqualitySkipTagged, "cs::valueEqualitySkipTagged", false);
@@ -0,0 +1,27 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::*;
+
+/// Makes the generated proxy methods of an operation return a `ValueTask` instead of a `Task`. This only changes the
+/// signature of these methods: they still wrap the `Task` returned by `InvokeAsync`, so marking an operation with
+/// `cs::valueTask` doesn't save the allocation of this `Task`.
+#[derive(Debug)]
+pub struct CsValueTask {}
+
+impl CsValueTask {
+    pub fn parse_from(Unparsed { directive, args }: &Unparsed, span: &Span, diagnostics: &mut Diagnostics) -> Self {
+        debug_assert_eq!(directive, Self::directive());
+
+        check_that_no_arguments_were_provided(args, Self::directive(), span, diagnostics);
+
+        CsValueTask {}
+    }
+
+    pub fn validate_on(&self, applied_on: Attributables, span: &Span, diagnostics: &mut Diagnostics) {
+        if !matches!(applied_on, Attributables::Operation(_)) {
+            report_unexpected_attribute(self, span, None, diagnostics);
+        }
+    }
+}
+
+implement_attribute_kind_for!(CsValueTask, "cs::valueTask", false);
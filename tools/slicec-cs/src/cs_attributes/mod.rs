@@ -1,20 +1,110 @@
 // Copyright (c) ZeroC, Inc.
 
 mod cs_attribute;
+mod cs_buffer_writer_extension;
+mod cs_builder;
+mod cs_case_insensitive;
+mod cs_class;
+mod cs_comparable;
+mod cs_custom_default;
+mod cs_data_contract;
+mod cs_decode;
+mod cs_decode_bytes;
+mod cs_deconstruct;
+mod cs_deep_clone;
+mod cs_delegates;
+mod cs_encode;
+mod cs_encode_bytes;
+mod cs_encode_options;
+mod cs_encode_order;
+mod cs_encode_to_array;
 mod cs_encoded_return;
+mod cs_encoded_size;
+mod cs_fast_equals;
+mod cs_field_tags;
+mod cs_flags;
+mod cs_formattable;
+mod cs_framed;
+mod cs_frozen;
 mod cs_identifier;
+mod cs_if_defined;
+mod cs_init;
+mod cs_intern;
 mod cs_internal;
+mod cs_json;
+mod cs_lifecycle_hooks;
+mod cs_memory;
+mod cs_mutable;
 mod cs_namespace;
+mod cs_numeric;
+mod cs_obsolete_error;
+mod cs_pipe_reader_extension;
 mod cs_readonly;
+mod cs_readonly_list;
+mod cs_required;
+mod cs_sealed;
+mod cs_stable_hash;
+mod cs_struct_layout;
+mod cs_to_string;
+mod cs_try_decode;
+mod cs_try_encode;
 mod cs_type;
+mod cs_validate;
+mod cs_value_equality_skip_tagged;
+mod cs_value_task;
 
 pub use cs_attribute::*;
+pub use cs_buffer_writer_extension::*;
+pub use cs_builder::*;
+pub use cs_case_insensitive::*;
+pub use cs_class::*;
+pub use cs_comparable::*;
+pub use cs_custom_default::*;
+pub use cs_data_contract::*;
+pub use cs_decode::*;
+pub use cs_decode_bytes::*;
+pub use cs_deconstruct::*;
+pub use cs_deep_clone::*;
+pub use cs_delegates::*;
+pub use cs_encode::*;
+pub use cs_encode_bytes::*;
+pub use cs_encode_options::*;
+pub use cs_encode_order::*;
+pub use cs_encode_to_array::*;
 pub use cs_encoded_return::*;
+pub use cs_encoded_size::*;
+pub use cs_fast_equals::*;
+pub use cs_field_tags::*;
+pub use cs_flags::*;
+pub use cs_formattable::*;
+pub use cs_framed::*;
+pub use cs_frozen::*;
 pub use cs_identifier::*;
+pub use cs_if_defined::*;
+pub use cs_init::*;
+pub use cs_intern::*;
 pub use cs_internal::*;
+pub use cs_json::*;
+pub use cs_lifecycle_hooks::*;
+pub use cs_memory::*;
+pub use cs_mutable::*;
 pub use cs_namespace::*;
+pub use cs_numeric::*;
+pub use cs_obsolete_error::*;
+pub use cs_pipe_reader_extension::*;
 pub use cs_readonly::*;
+pub use cs_readonly_list::*;
+pub use cs_required::*;
+pub use cs_sealed::*;
+pub use cs_stable_hash::*;
+pub use cs_struct_layout::*;
+pub use cs_to_string::*;
+pub use cs_try_decode::*;
+pub use cs_try_encode::*;
 pub use cs_type::*;
+pub use cs_validate::*;
+pub use cs_value_equality_skip_tagged::*;
+pub use cs_value_task::*;
 
 use slicec::diagnostics::{Diagnostic, Diagnostics, Error};
 use slicec::grammar::attributes::*;
@@ -1,23 +1,72 @@
 // Copyright (c) ZeroC, Inc.
 
 use crate::cs_attributes::*;
+use crate::cs_options::CsOptions;
+use crate::member_util::is_mutable_collection;
+use crate::slicec_ext::{FieldExt, MemberExt, TypeRefExt};
 use slicec::ast::node::Node;
 use slicec::compilation_state::CompilationState;
-use slicec::diagnostics::{Diagnostic, Error};
+use slicec::diagnostics::{Diagnostic, Diagnostics, Error, Lint};
 use slicec::grammar::attributes::Unparsed;
-use slicec::grammar::{AttributeFunctions, Symbol};
+use slicec::grammar::{AttributeFunctions, Field, Member, NamedSymbol, Symbol, TypeRef, Types};
+use slicec::slice_file::Span;
 use std::io;
 
 pub unsafe fn cs_patcher(compilation_state: &mut CompilationState) {
     let attribute_patcher = slicec::patch_attributes!(
         "cs::",
         CsAttribute,
+        CsBufferWriterExtension,
+        CsBuilder,
+        CsCaseInsensitive,
+        CsClass,
+        CsComparable,
+        CsCustomDefault,
+        CsDataContract,
+        CsDecode,
+        CsDecodeBytes,
+        CsDeconstruct,
+        CsDeepClone,
+        CsDelegates,
+        CsEncode,
+        CsEncodeBytes,
+        CsEncodeOptions,
+        CsEncodeOrder,
+        CsEncodeToArray,
         CsEncodedReturn,
+        CsEncodedSize,
+        CsFastEquals,
+        CsFieldTags,
+        CsFlags,
+        CsFormattable,
+        CsFramed,
+        CsFrozen,
         CsIdentifier,
+        CsIfDefined,
+        CsInit,
+        CsIntern,
         CsInternal,
+        CsJson,
+        CsLifecycleHooks,
+        CsMemory,
+        CsMutable,
         CsNamespace,
+        CsNumeric,
+        CsObsoleteError,
+        CsPipeReaderExtension,
         CsReadonly,
+        CsReadonlyList,
+        CsRequired,
+        CsSealed,
+        CsStableHash,
+        CsStructLayout,
+        CsToString,
+        CsTryDecode,
+        CsTryEncode,
         CsType,
+        CsValidate,
+        CsValueEqualitySkipTagged,
+        CsValueTask,
     );
     compilation_state.apply_unsafe(attribute_patcher);
 }
@@ -25,6 +74,12 @@ pub unsafe fn cs_patcher(compilation_state: &mut CompilationState) {
 pub fn cs_validator(compilation_state: &mut CompilationState) {
     compilation_state.apply(check_for_unique_names);
     compilation_state.apply(ensure_custom_types_have_type_attribute);
+    compilation_state.apply(check_for_unique_field_names);
+    compilation_state.apply(check_for_field_tags_collisions);
+    compilation_state.apply(check_encode_orders);
+    compilation_state.apply(check_for_sealed_base_types);
+    compilation_state.apply(check_readonly_structs_for_mutable_collections);
+    compilation_state.apply(check_for_internal_types_in_public_operations);
 }
 
 fn check_for_unique_names(compilation_state: &mut CompilationState) {
@@ -62,11 +117,315 @@ fn ensure_custom_types_have_type_attribute(compilation_state: &mut CompilationSt
     }
 }
 
+/// Checks that no two fields of the same type map to the same C# identifier, which can happen when fields are renamed
+/// with `cs::identifier` or by the case conversion of their identifiers. Fields with the same Slice identifier are
+/// skipped, since slicec already reports them.
+fn check_for_unique_field_names(compilation_state: &mut CompilationState) {
+    for node in compilation_state.ast.as_slice() {
+        let fields = match node {
+            Node::Struct(struct_ptr) => struct_ptr.borrow().fields(),
+            Node::Class(class_ptr) => class_ptr.borrow().fields(),
+            Node::Exception(exception_ptr) => exception_ptr.borrow().fields(),
+            Node::Enumerator(enumerator_ptr) => enumerator_ptr.borrow().fields(),
+            _ => continue,
+        };
+        check_fields_for_unique_names(&fields, &mut compilation_state.diagnostics);
+    }
+}
+
+fn check_fields_for_unique_names(fields: &[&Field], diagnostics: &mut Diagnostics) {
+    let mut field_map = std::collections::HashMap::new();
+
+    for field in fields {
+        let field_name = field.field_name();
+        let other_field = field_map.insert(field_name.clone(), *field);
+        if let Some(other_field) = other_field.filter(|other_field| other_field.identifier() != field.identifier()) {
+            Diagnostic::new(Error::Redefinition {
+                identifier: field_name.clone(),
+            })
+            .set_span(field.span())
+            .add_note(
+                format!(
+                    "Field '{}' is mapped to the C# identifier '{field_name}', which is already used by field '{}'.",
+                    field.identifier(),
+                    other_field.identifier(),
+                ),
+                Some(other_field.span()),
+            )
+            .push_into(diagnostics);
+        }
+    }
+}
+
+/// Checks that no field of a struct marked with `cs::fieldTags` is mapped to the C# identifier `FieldTags`, which is
+/// already used by the static dictionary generated for these structs.
+fn check_for_field_tags_collisions(compilation_state: &mut CompilationState) {
+    for node in compilation_state.ast.as_slice() {
+        let Node::Struct(struct_ptr) = node else {
+            continue;
+        };
+        let struct_def = struct_ptr.borrow();
+        if !struct_def.has_attribute::<CsFieldTags>() {
+            continue;
+        }
+
+        for field in struct_def.fields() {
+            if field.field_name() == "FieldTags" {
+                Diagnostic::new(Error::Redefinition {
+                    identifier: "FieldTags".to_owned(),
+                })
+                .set_span(field.span())
+                .add_note(
+                    format!(
+                        "Field '{}' is mapped to the C# identifier 'FieldTags', which is already used by the generated 'FieldTags' dictionary of struct '{}'.",
+                        field.identifier(),
+                        struct_def.identifier(),
+                    ),
+                    None,
+                )
+                .push_into(&mut compilation_state.diagnostics);
+            }
+        }
+    }
+}
+
+/// Checks that no class or exception derives from a class or exception marked with `cs::sealed`.
+fn check_for_sealed_base_types(compilation_state: &mut CompilationState) {
+    for node in compilation_state.ast.as_slice() {
+        match node {
+            Node::Class(class_ptr) => {
+                let class_def = class_ptr.borrow();
+                if let Some(base) = class_def.base_class() {
+                    check_base_type_is_not_sealed(class_def, base, &mut compilation_state.diagnostics);
+                }
+            }
+            Node::Exception(exception_ptr) => {
+                let exception_def = exception_ptr.borrow();
+                if let Some(base) = exception_def.base_exception() {
+                    check_base_type_is_not_sealed(exception_def, base, &mut compilation_state.diagnostics);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_base_type_is_not_sealed(
+    derived: &impl NamedSymbol,
+    base: &(impl NamedSymbol + AttributeFunctions),
+    diagnostics: &mut Diagnostics,
+) {
+    if base.has_attribute::<CsSealed>() {
+        Diagnostic::new(Error::UnexpectedAttribute {
+            attribute: CsSealed::directive().to_owned(),
+        })
+        .set_span(derived.span())
+        .add_note(
+            format!(
+                "'{}' derives from '{}', which is marked with '{}'.",
+                derived.identifier(),
+                base.identifier(),
+                CsSealed::directive(),
+            ),
+            Some(base.span()),
+        )
+        .push_into(diagnostics);
+    }
+}
+
+/// Reports a warning for each field of a struct marked with `cs::readonly` whose type is a mutable collection. The
+/// struct is still generated as readonly, but the contents of these collections can be modified through it.
+fn check_readonly_structs_for_mutable_collections(compilation_state: &mut CompilationState) {
+    for node in compilation_state.ast.as_slice() {
+        let Node::Struct(struct_ptr) = node else {
+            continue;
+        };
+        let struct_def = struct_ptr.borrow();
+        if !struct_def.has_attribute::<CsReadonly>() {
+            continue;
+        }
+
+        for field in struct_def.fields() {
+            if is_mutable_collection(field) {
+                let message = format!(
+                    "field '{}' of readonly struct '{}' is a mutable collection. Consider mapping it with '{}' or to an immutable collection type.",
+                    field.identifier(),
+                    struct_def.identifier(),
+                    CsReadonlyList::directive(),
+                );
+                report_warning(message, field.span(), &mut compilation_state.diagnostics);
+            }
+        }
+    }
+}
+
+/// Reports a warning for each struct field marked with an attribute which isn't supported by the target framework.
+/// Required fields are generated as regular properties, but frozen dictionaries can't be replaced, since they're part
+/// of the field's type. Unlike the other checks, this one depends on the options, so it's run after the validator.
+pub fn check_target_framework_support(compilation_state: &mut CompilationState, options: &CsOptions) {
+    if options.target_framework.supports_modern_features() {
+        return;
+    }
+
+    for node in compilation_state.ast.as_slice() {
+        let Node::Struct(struct_ptr) = node else {
+            continue;
+        };
+        let struct_def = struct_ptr.borrow();
+
+        for field in struct_def.fields() {
+            let unsupported_attribute = if field.is_cs_required() {
+                CsRequired::directive()
+            } else if field.data_type().is_frozen_dictionary() {
+                CsFrozen::directive()
+            } else {
+                continue;
+            };
+
+            let message = format!(
+                "field '{}' of struct '{}' is marked with '{}', which isn't supported by the target framework.",
+                field.identifier(),
+                struct_def.identifier(),
+                unsupported_attribute,
+            );
+            report_warning(message, field.span(), &mut compilation_state.diagnostics);
+        }
+    }
+}
+
+/// Reports a warning about the C# code generated for a Slice definition. slicec's lints are a fixed set, so these
+/// warnings are reported as `IncorrectDocComment` lints, which can be allowed like any other lint.
+pub fn report_warning(message: String, span: &Span, diagnostics: &mut Diagnostics) {
+    Diagnostic::new(Lint::IncorrectDocComment { message })
+        .set_span(span)
+        .push_into(diagnostics);
+}
+
+/// Checks that the encode orders of a struct's fields form a complete permutation: when any field of a struct is marked
+/// with `cs::encodeOrder`, all its non-tagged fields must be marked, and their orders must be `0` to `n - 1` without any
+/// gaps or duplicates.
+fn check_encode_orders(compilation_state: &mut CompilationState) {
+    for node in compilation_state.ast.as_slice() {
+        let Node::Struct(struct_ptr) = node else {
+            continue;
+        };
+        let struct_def = struct_ptr.borrow();
+        let fields = struct_def.fields();
+        let ordered_fields = fields.iter().filter(|field| !field.is_tagged()).collect::<Vec<_>>();
+        if !ordered_fields
+            .iter()
+            .any(|field| field.has_attribute::<CsEncodeOrder>())
+        {
+            continue;
+        }
+
+        let mut order_map = std::collections::HashMap::new();
+        for field in &ordered_fields {
+            let Some(encode_order) = field.find_attribute::<CsEncodeOrder>() else {
+                Diagnostic::new(Error::MissingRequiredAttribute {
+                    attribute: CsEncodeOrder::directive().to_owned(),
+                })
+                .set_span(field.span())
+                .add_note(
+                    format!(
+                        "All the non-tagged fields of struct '{}' must be marked with '{}' when any of them is.",
+                        struct_def.identifier(),
+                        CsEncodeOrder::directive(),
+                    ),
+                    None,
+                )
+                .push_into(&mut compilation_state.diagnostics);
+                continue;
+            };
+
+            let order = encode_order.order;
+            let note = if order >= ordered_fields.len() {
+                format!(
+                    "The encode orders of struct '{}' must range from 0 to {} without gaps.",
+                    struct_def.identifier(),
+                    ordered_fields.len() - 1,
+                )
+            } else if let Some(other_field) = order_map.insert(order, *field) {
+                format!(
+                    "Encode order '{order}' is already used by field '{}'.",
+                    other_field.identifier(),
+                )
+            } else {
+                continue;
+            };
+            Diagnostic::new(Error::ArgumentNotSupported {
+                argument: order.to_string(),
+                directive: CsEncodeOrder::directive().to_owned(),
+            })
+            .set_span(field.span())
+            .add_note(note, None)
+            .push_into(&mut compilation_state.diagnostics);
+        }
+    }
+}
+
+/// Checks that types marked with `cs::internal` aren't used by the operations of public interfaces, since a public
+/// member cannot expose a type that is less accessible than itself in C#.
+fn check_for_internal_types_in_public_operations(compilation_state: &mut CompilationState) {
+    for node in compilation_state.ast.as_slice() {
+        let Node::Operation(operation_ptr) = node else {
+            continue;
+        };
+        let operation = operation_ptr.borrow();
+        let interface_def = operation.parent();
+        if interface_def.has_attribute::<CsInternal>() {
+            continue;
+        }
+
+        for member in operation.parameters().into_iter().chain(operation.return_members()) {
+            if let Some((identifier, span)) = find_internal_type(member.data_type()) {
+                Diagnostic::new(Error::UnexpectedAttribute {
+                    attribute: CsInternal::directive().to_owned(),
+                })
+                .set_span(member.span())
+                .add_note(
+                    format!(
+                        "'{identifier}' is marked with '{}', so it cannot be used by operation '{}' of the public interface '{}'.",
+                        CsInternal::directive(),
+                        operation.identifier(),
+                        interface_def.identifier(),
+                    ),
+                    Some(&span),
+                )
+                .push_into(&mut compilation_state.diagnostics);
+            }
+        }
+    }
+}
+
+/// Returns the identifier and span of the first type marked with `cs::internal` that is referenced by the provided
+/// type, including through the element types of sequences, dictionaries and results.
+fn find_internal_type(type_ref: &TypeRef) -> Option<(String, Span)> {
+    match type_ref.concrete_type() {
+        Types::Struct(struct_def) if struct_def.has_attribute::<CsInternal>() => {
+            Some((struct_def.identifier().to_owned(), struct_def.span().clone()))
+        }
+        Types::Class(class_def) if class_def.has_attribute::<CsInternal>() => {
+            Some((class_def.identifier().to_owned(), class_def.span().clone()))
+        }
+        Types::Enum(enum_def) if enum_def.has_attribute::<CsInternal>() => {
+            Some((enum_def.identifier().to_owned(), enum_def.span().clone()))
+        }
+        Types::Sequence(sequence_def) => find_internal_type(&sequence_def.element_type),
+        Types::Dictionary(dictionary_def) => {
+            find_internal_type(&dictionary_def.key_type).or_else(|| find_internal_type(&dictionary_def.value_type))
+        }
+        Types::ResultType(result_type_def) => find_internal_type(&result_type_def.success_type)
+            .or_else(|| find_internal_type(&result_type_def.failure_type)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{check_for_unique_names, cs_patcher, cs_validator};
     use crate::cs_options::CsOptions;
-    use crate::generators::generate_from_slice_file;
+    use crate::generators::{generate_from_slice_file, generate_plumbing_from_slice_file};
     use slicec::compilation_state::CompilationState;
     use slicec::diagnostics::{Diagnostic, Diagnostics, Error};
     use slicec::slice_file::SliceFile;
@@ -120,8 +479,9 @@ mod test {
                 // Note that these generators return the generated code as a String, they don't actually write to disk.
                 let parsed_slice_file = compilation_state.files.first().unwrap();
                 assert!(parsed_slice_file.relative_path == slice_file.relative_path);
-                generate_from_slice_file(compilation_state.files.first().unwrap(), false, &cs_options);
-                generate_from_slice_file(compilation_state.files.first().unwrap(), true, &cs_options);
+                generate_from_slice_file(parsed_slice_file, false, &cs_options);
+                generate_from_slice_file(parsed_slice_file, true, &cs_options);
+                generate_plumbing_from_slice_file(parsed_slice_file, &cs_options);
             }
         }
     }
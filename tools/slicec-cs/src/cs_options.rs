@@ -22,6 +22,67 @@ pub struct CsOptions {
     #[arg(long)]
     pub telemetry: bool,
 
+    /// Replace the header comment at the top of generated files with the provided text.
+    /// '{version}' and '{file}' are replaced with the slicec-cs version and the name of the Slice file.
+    #[arg(long, value_name = "HEADER")]
+    pub generated_file_header: Option<String>,
+
+    /// Write the code generated for all the Slice files into a single C# file with the provided name.
+    #[arg(long, value_name = "FILENAME")]
+    pub output_single_file: Option<String>,
+
+    /// Hide the generated members that are only used for encoding and decoding from IntelliSense, and mark the encoding
+    /// and decoding members of structs as compiler-generated, to exclude them from code coverage.
+    #[arg(long)]
+    pub hide_generated_plumbing: bool,
+
+    /// Add 'DebuggerDisplay' attributes to the generated structs, displaying their first scalar fields.
+    #[arg(long)]
+    pub debugger_display: bool,
+
+    /// Don't append '.ConfigureAwait(false)' to the calls awaited by the generated proxies.
+    #[arg(long)]
+    pub continue_on_captured_context: bool,
+
+    /// Treat warnings as errors: fail the compilation without generating any code if a warning is reported.
+    /// Warnings can still be suppressed with '--allow' and with 'allow' attributes.
+    #[arg(long)]
+    pub warnings_as_errors: bool,
+
+    /// Ask the JIT compiler to inline the generated encoding and decoding methods whose body is only a few lines long.
+    #[arg(long)]
+    pub aggressive_inlining: bool,
+
+    /// Generate structs as plain C# structs and classes instead of records. Their equality members and 'ToString' are
+    /// generated explicitly, instead of being synthesized by the C# compiler.
+    #[arg(long)]
+    pub explicit_constructors_only: bool,
+
+    /// Don't enable nullable reference types in the generated files. Instead, annotate each generated field with the
+    /// 'System.Diagnostics.CodeAnalysis' attribute matching its nullability. For projects that keep nullable disabled.
+    #[arg(long)]
+    pub emit_nullable_context_per_member: bool,
+
+    /// Generate structs as partial types split across two files: their fields and other members in '<file>.cs', and
+    /// their encoding and decoding members in '<file>.Slice.cs'. Ignored with '--output-single-file'.
+    #[arg(long)]
+    pub split_generated: bool,
+
+    /// Add 'SkipLocalsInit' attributes to the generated 'Encode' methods and decode constructors of structs, so their
+    /// locals aren't zero-initialized. Projects using this option must allow unsafe code ('AllowUnsafeBlocks').
+    #[arg(long)]
+    pub skip_locals_init: bool,
+
+    /// Grant the provided friend assembly access to the generated internal types, with an 'InternalsVisibleTo'
+    /// attribute. Can be repeated. The attributes are written once, into a 'SliceAssemblyInfo.cs' file.
+    #[arg(long, value_name = "ASSEMBLY")]
+    pub internals_visible_to: Vec<String>,
+
+    /// Generate code for the specified .NET version. Features that aren't available on older versions are omitted or
+    /// replaced, and a warning is reported for the attributes requesting them.
+    #[arg(long, value_enum, default_value_t = TargetFramework::Net8, ignore_case = true)]
+    pub target_framework: TargetFramework,
+
     // Import the options common to all slice compilers.
     #[command(flatten)]
     pub slice_options: SliceOptions,
@@ -35,6 +96,19 @@ impl Default for CsOptions {
         CsOptions {
             rpc_provider: RpcProvider::default(),
             telemetry: false,
+            generated_file_header: None,
+            output_single_file: None,
+            hide_generated_plumbing: false,
+            debugger_display: false,
+            continue_on_captured_context: false,
+            warnings_as_errors: false,
+            aggressive_inlining: false,
+            explicit_constructors_only: false,
+            emit_nullable_context_per_member: false,
+            split_generated: false,
+            skip_locals_init: false,
+            internals_visible_to: Vec::new(),
+            target_framework: TargetFramework::default(),
             slice_options,
         }
     }
@@ -58,3 +132,25 @@ pub enum RpcProvider {
     #[clap(name = "icerpc")]
     IceRpc,
 }
+
+/// This enum is used to specify which .NET version the generated code targets.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum TargetFramework {
+    /// .NET 6, which doesn't support required members, frozen collections, or the generic math and parsing
+    /// interfaces.
+    #[clap(name = "net6.0")]
+    Net6,
+
+    /// .NET 8 or later, which supports all the features used by the generated code.
+    #[default]
+    #[clap(name = "net8.0")]
+    Net8,
+}
+
+impl TargetFramework {
+    /// Returns true if this framework supports the features introduced by .NET 7 and .NET 8: required members, frozen
+    /// collections, and the generic math and parsing interfaces.
+    pub fn supports_modern_features(self) -> bool {
+        self == TargetFramework::Net8
+    }
+}
@@ -189,7 +189,7 @@ pub fn format_comment_message(message: &Message, namespace: &str) -> String {
     // If the component is text, escape XML entities and then append it. If the component is a link, format it first,
     // then append it.
     let message_components = message.value.iter();
-    message_components.fold(String::new(), |s, component| match &component {
+    let formatted_message = message_components.fold(String::new(), |s, component| match &component {
         MessageComponent::Text(text) => s + &xml_escape(text),
         MessageComponent::Link(link_tag) => match link_tag.linked_entity() {
             Ok(entity) => {
@@ -206,7 +206,66 @@ pub fn format_comment_message(message: &Message, namespace: &str) -> String {
             // If the link was broken, just output it's raw text.
             Err(identifier) => s + &identifier.value,
         },
-    })
+    });
+
+    // Convert any markdown-style code spans in the message into their corresponding C# doc comment tags.
+    format_code_spans(&formatted_message)
+}
+
+/// Replaces markdown-style code spans with C# doc comment tags. Fenced code blocks (delimited by "```") are replaced
+/// with `<code>` tags, and inline code spans (delimited by a single '`') are replaced with `<c>` tags.
+/// Any language identifier following the opening fence is dropped. Unterminated spans are left as-is.
+///
+/// Note that this function expects the provided text to already be XML escaped.
+fn format_code_spans(text: &str) -> String {
+    let mut result = String::new();
+
+    let blocks = text.split("```").collect::<Vec<_>>();
+    for (index, block) in blocks.iter().enumerate() {
+        if index % 2 == 0 {
+            // Even segments are outside of any fenced code block, but can still contain inline code spans.
+            result += &format_inline_code_spans(block);
+        } else if index == blocks.len() - 1 {
+            // This code block was never terminated, so we output it as-is.
+            result = result + "```" + &format_inline_code_spans(block);
+        } else {
+            // Odd segments are the contents of fenced code blocks. If the opening fence is followed by a language
+            // identifier (ie. "```slice"), we strip it from the output.
+            let code = match block.split_once('\n') {
+                Some((first_line, rest)) if !first_line.trim().contains(char::is_whitespace) => rest,
+                _ => block,
+            };
+            result = result + "<code>" + code.trim_end() + "</code>";
+        }
+    }
+    result
+}
+
+/// Replaces inline markdown-style code spans (delimited by a single '`') with `<c>` tags.
+/// Unterminated code spans are left as-is.
+fn format_inline_code_spans(text: &str) -> String {
+    let mut result = String::new();
+
+    let spans = text.split('`').collect::<Vec<_>>();
+    for (index, span) in spans.iter().enumerate() {
+        if index % 2 == 0 {
+            result += span;
+        } else if index == spans.len() - 1 {
+            // This code span was never terminated, so we output it as-is.
+            result = result + "`" + span;
+        } else {
+            result = result + "<c>" + span + "</c>";
+        }
+    }
+    result
+}
+
+/// Escapes the provided text so it can be embedded in a double-quoted C# string literal.
+pub fn escape_string_literal(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
 }
 
 fn xml_escape(text: &str) -> String {
@@ -246,4 +305,22 @@ mod test {
     fn cs_camel_case(input: &str, expected: &str) {
         assert_eq!(input.to_cs_case(Case::Camel), expected);
     }
+
+    #[test_case("reason", "reason"; "plain")]
+    #[test_case(r#"use "Foo" instead"#, r#"use \"Foo\" instead"#; "quotes")]
+    #[test_case(r"C:\path", r"C:\\path"; "backslash")]
+    #[test_case("line1\nline2", r"line1\nline2"; "newline")]
+    fn string_literal_escaping(input: &str, expected: &str) {
+        assert_eq!(escape_string_literal(input), expected);
+    }
+
+    #[test_case("Call `foo` now.", "Call <c>foo</c> now."; "inline_code")]
+    #[test_case("`a` and `b`", "<c>a</c> and <c>b</c>"; "multiple_inline_code")]
+    #[test_case("An `unterminated span", "An `unterminated span"; "unterminated_inline_code")]
+    #[test_case("Use `List&lt;T&gt;`.", "Use <c>List&lt;T&gt;</c>."; "escaped_inline_code")]
+    #[test_case("Example:\n```\nfoo();\n```", "Example:\n<code>foo();</code>"; "fenced_code")]
+    #[test_case("```slice\nstruct S {}\n```", "<code>struct S {}</code>"; "fenced_code_with_language")]
+    fn code_spans(input: &str, expected: &str) {
+        assert_eq!(format_code_spans(input), expected);
+    }
 }
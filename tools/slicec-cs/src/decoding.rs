@@ -3,7 +3,7 @@
 use crate::builders::{Builder, FunctionCallBuilder};
 use crate::code_block::CodeBlock;
 use crate::code_gen_util::get_bit_sequence_size;
-use crate::cs_attributes::CsType;
+use crate::cs_attributes::{CsDecode, CsIntern, CsType};
 use crate::cs_util::*;
 use crate::member_util::get_sorted_members;
 use crate::slicec_ext::*;
@@ -24,12 +24,46 @@ fn initialize_bit_sequence_reader_for<T: Member>(members: &[&T], code: &mut Code
 
 pub fn decode_fields(fields: &[&Field], encoding: Encoding) -> CodeBlock {
     let mut code = CodeBlock::default();
-    initialize_bit_sequence_reader_for(fields, &mut code, encoding);
+    for block in decode_field_blocks(fields, encoding) {
+        code.writeln(&block);
+    }
+    code
+}
 
-    let action = |field_name, field_value| writeln!(code, "this.{field_name} = {field_value};");
+/// Generates the code for decoding the provided fields, with a separate code block for each field.
+/// The first block holds the creation of the bit sequence reader, and is empty if no bit sequence is needed.
+pub fn decode_field_blocks(fields: &[&Field], encoding: Encoding) -> Vec<CodeBlock> {
+    let mut bit_sequence_block = CodeBlock::default();
+    initialize_bit_sequence_reader_for(fields, &mut bit_sequence_block, encoding);
+
+    let mut blocks = vec![bit_sequence_block];
+    let action = |field_name, field_value| blocks.push(format!("this.{field_name} = {field_value};").into());
 
     decode_fields_core(fields, encoding, action);
-    code
+    blocks
+}
+
+/// Generates an expression for decoding each of the provided fields, in the order of the fields. These expressions can
+/// be passed directly as the arguments of a constructor, but only when the fields are decoded in the order they were
+/// declared in, and without a bit sequence reader (which must be declared as a local variable). Read-only lists are
+/// also excluded, since they would be copied a second time by the constructor. Returns `None` for any other fields.
+pub fn decode_field_arguments(fields: &[&Field], encoding: Encoding) -> Option<Vec<String>> {
+    let is_in_declaration_order = get_sorted_members(fields)
+        .zip(fields)
+        .all(|(sorted, field)| sorted.identifier() == field.identifier());
+    if !is_in_declaration_order
+        || get_bit_sequence_size(encoding, fields) > 0
+        || fields.iter().any(|field| field.is_cs_readonly_list())
+    {
+        return None;
+    }
+
+    let mut arguments = Vec::new();
+    let action = |_, field_value: CodeBlock| {
+        arguments.push(field_value.to_string().trim_end_matches(';').to_owned());
+    };
+    decode_fields_core(fields, encoding, action);
+    Some(arguments)
 }
 
 pub fn decode_enum_fields(fields: &[&Field], enum_class: &str, encoding: Encoding) -> CodeBlock {
@@ -52,10 +86,18 @@ fn decode_fields_core(fields: &[&Field], encoding: Encoding, mut action: impl Fn
     for field in get_sorted_members(fields) {
         let namespace = field.namespace();
 
-        let field_name = field.field_name();
-        let field_value = match field.is_tagged() {
-            true => decode_tagged(field, &namespace, true, encoding),
-            false => decode_member(field, &namespace, encoding),
+        // Read-only lists are decoded directly into their backing array.
+        let field_name = match field.is_cs_readonly_list() {
+            true => field.backing_field_name(),
+            false => field.field_name(),
+        };
+        // Fields mapped to a custom C# type are decoded with the method provided by the user.
+        let field_value = match field.find_attribute::<CsDecode>() {
+            Some(decode) => format!("{}(ref decoder)", decode.method).into(),
+            // String fields marked with `cs::intern` are interned right after being decoded.
+            None if field.has_attribute::<CsIntern>() => decode_interned_string(field, encoding),
+            None if field.is_tagged() => decode_tagged(field, &namespace, true, encoding),
+            None => decode_member(field, &namespace, encoding),
         };
 
         action(field_name, field_value);
@@ -143,6 +185,16 @@ fn decode_member(member: &impl Member, namespace: &str, encoding: Encoding) -> C
 }
 
 fn decode_tagged(member: &impl Member, namespace: &str, constructed_type: bool, encoding: Encoding) -> CodeBlock {
+    let decode_func = decode_func(member.data_type(), namespace, encoding);
+    decode_tagged_with(member, decode_func, constructed_type, encoding)
+}
+
+fn decode_tagged_with(
+    member: &impl Member,
+    decode_func: CodeBlock,
+    constructed_type: bool,
+    encoding: Encoding,
+) -> CodeBlock {
     let data_type = member.data_type();
 
     assert!(data_type.is_optional);
@@ -153,7 +205,7 @@ fn decode_tagged(member: &impl Member, namespace: &str, constructed_type: bool,
         .add_argument_if_present(
             (encoding == Encoding::Slice1).then(|| format!("TagFormat.{}", data_type.tag_format().unwrap())),
         )
-        .add_argument(decode_func(data_type, namespace, encoding))
+        .add_argument(decode_func)
         .add_argument_if_present((encoding == Encoding::Slice1).then(|| format!("useTagEndMarker: {constructed_type}")))
         .use_semicolon(false)
         .build();
@@ -161,6 +213,20 @@ fn decode_tagged(member: &impl Member, namespace: &str, constructed_type: bool,
     decode
 }
 
+fn decode_interned_string(field: &Field, encoding: Encoding) -> CodeBlock {
+    let intern = field.find_attribute::<CsIntern>().unwrap();
+    let decode_string = intern.intern_call("decoder.DecodeString()");
+
+    if field.is_tagged() {
+        let decode_func = format!("(ref SliceDecoder decoder) => (string?){decode_string}");
+        decode_tagged_with(field, decode_func.into(), true, encoding)
+    } else if field.data_type().is_optional {
+        format!("bitSequenceReader.Read() ? {decode_string} : null").into()
+    } else {
+        decode_string.into()
+    }
+}
+
 fn decode_dictionary(dictionary_ref: &TypeRef<Dictionary>, namespace: &str, encoding: Encoding) -> CodeBlock {
     let key_type = &dictionary_ref.key_type;
     let value_type = &dictionary_ref.value_type;
@@ -168,12 +234,22 @@ fn decode_dictionary(dictionary_ref: &TypeRef<Dictionary>, namespace: &str, enco
     // decode key
     let decode_key = decode_func(key_type, namespace, encoding);
     let decode_value = decode_func_with_cast(value_type, namespace, encoding, false);
-    let dictionary_type = remove_optional_modifier_from(dictionary_ref.incoming_parameter_type_string(namespace));
+
+    // Frozen dictionaries are decoded into a regular dictionary, which is frozen once it's fully populated.
+    let is_frozen = dictionary_ref.is_frozen_dictionary();
+    let dictionary_type = match is_frozen {
+        true => format!(
+            "global::System.Collections.Generic.Dictionary<{}, {}>",
+            key_type.field_type_string(namespace),
+            value_type.field_type_string(namespace),
+        ),
+        false => remove_optional_modifier_from(dictionary_ref.incoming_parameter_type_string(namespace)),
+    };
     let decode_key = decode_key.indent();
     let decode_value = decode_value.indent();
 
     // Use WithOptionalValueType method if encoding is not Slice1 and the value type is optional
-    if encoding != Encoding::Slice1 && value_type.is_optional {
+    let decode = if encoding != Encoding::Slice1 && value_type.is_optional {
         format!(
             "\
 decoder.DecodeDictionaryWithOptionalValueType(
@@ -189,6 +265,11 @@ decoder.DecodeDictionary(
     {decode_key},
     {decode_value})",
         )
+    };
+
+    match is_frozen {
+        true => format!("global::System.Collections.Frozen.FrozenDictionary.ToFrozenDictionary({decode})"),
+        false => decode,
     }
     .into()
 }
@@ -3,7 +3,7 @@
 use crate::builders::{Builder, FunctionCallBuilder};
 use crate::code_block::CodeBlock;
 use crate::code_gen_util::{get_bit_sequence_size, TypeContext};
-use crate::cs_attributes::CsType;
+use crate::cs_attributes::{CsEncode, CsType};
 use crate::member_util::get_sorted_members;
 use crate::slicec_ext::*;
 use convert_case::Case;
@@ -11,26 +11,43 @@ use slicec::grammar::*;
 
 pub fn encode_fields(fields: &[&Field], encoding: Encoding) -> CodeBlock {
     let mut code = CodeBlock::default();
+    for block in encode_field_blocks(fields, encoding) {
+        code.writeln(&block);
+    }
+    code
+}
 
+/// Generates the code for encoding the provided fields, with a separate code block for each field.
+/// The first block holds the creation of the bit sequence writer, and is empty if no bit sequence is needed.
+pub fn encode_field_blocks(fields: &[&Field], encoding: Encoding) -> Vec<CodeBlock> {
+    let mut bit_sequence_block = CodeBlock::default();
     let bit_sequence_size = get_bit_sequence_size(encoding, fields);
     if bit_sequence_size > 0 {
         writeln!(
-            code,
+            bit_sequence_block,
             "var bitSequenceWriter = encoder.GetBitSequenceWriter({bit_sequence_size});",
         );
     }
 
+    let mut blocks = vec![bit_sequence_block];
     for field in get_sorted_members(fields) {
         let namespace = field.namespace();
-        let param = format!("this.{}", field.field_name());
+        let param = match field.is_cs_readonly_list() {
+            true => format!("this.{}", field.backing_field_name()),
+            false => format!("this.{}", field.field_name()),
+        };
+        // Fields mapped to a custom C# type are encoded with the method provided by the user.
+        if let Some(encode) = field.find_attribute::<CsEncode>() {
+            blocks.push(format!("{}(ref encoder, {param});", encode.method).into());
+            continue;
+        }
         let encode_fn = match field.is_tagged() {
             true => encode_tagged_type,
             false => encode_type,
         };
-        code.writeln(&encode_fn(field, TypeContext::Field, &namespace, &param, "encoder", encoding));
+        blocks.push(encode_fn(field, TypeContext::Field, &namespace, &param, "encoder", encoding));
     }
-
-    code
+    blocks
 }
 
 fn encode_type(
@@ -169,7 +186,8 @@ fn encode_tagged_type(
     };
 
     // For types with a known size, we provide a size parameter with the size of the tagged
-    // param/member:
+    // param/member. Sizes computed from element counts are checked, so an overflow throws instead of producing
+    // a negative size:
     let (size_parameter, count_value) = match data_type.concrete_type() {
         Types::Primitive(primitive_def) => match primitive_def {
             Primitive::VarInt32 | Primitive::VarInt62 => {
@@ -191,14 +209,14 @@ fn encode_tagged_type(
                 } else if read_only_memory {
                     (
                         Some(format!(
-                            "{encoder_param}.GetSizeLength({value}.Length) + {element_size} * {value}.Length",
+                            "checked({encoder_param}.GetSizeLength({value}.Length) + {element_size} * {value}.Length)",
                         )),
                         None,
                     )
                 } else {
                     (
                         Some(format!(
-                            "{encoder_param}.GetSizeLength(count_) + {element_size} * count_",
+                            "checked({encoder_param}.GetSizeLength(count_) + {element_size} * count_)",
                         )),
                         Some(value.clone()),
                     )
@@ -215,7 +233,9 @@ fn encode_tagged_type(
             ) {
                 let size = key_size + value_size;
                 (
-                    Some(format!("{encoder_param}.GetSizeLength(count_) + ({size}) * count_",)),
+                    Some(format!(
+                        "checked({encoder_param}.GetSizeLength(count_) + {size} * count_)"
+                    )),
                     Some(value.clone()),
                 )
             } else {
@@ -274,7 +294,7 @@ fn encode_sequence(
     encoding: Encoding,
 ) -> CodeBlock {
     if sequence_ref.has_fixed_size_primitive_elements() && !sequence_ref.has_attribute::<CsType>() {
-        if type_context == TypeContext::OutgoingParam {
+        if type_context == TypeContext::OutgoingParam || sequence_ref.is_read_only_memory() {
             format!("{encoder_param}.EncodeSpan({value}.Span)")
         } else {
             format!("{encoder_param}.EncodeSequence({value})")
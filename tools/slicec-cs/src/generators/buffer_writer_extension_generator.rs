@@ -0,0 +1,117 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::struct_generator::{check_encoding_parameter, cs_encodings};
+use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
+use crate::code_block::CodeBlock;
+use crate::slicec_ext::EntityExt;
+use slicec::grammar::*;
+
+/// Generates a static class with an `Encode` extension method for `IBufferWriter<byte>`, which encodes the provided
+/// struct directly into a buffer writer, such as a `PipeWriter`.
+pub fn generate_buffer_writer_extension(struct_def: &Struct) -> CodeBlock {
+    let access = struct_def.access_modifier();
+    let escaped_identifier = struct_def.escape_identifier();
+    let encodings = cs_encodings(struct_def);
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "{}", check_encoding_parameter(struct_def, &encodings));
+    writeln!(
+        body,
+        "\
+var encoder = new SliceEncoder(writer, encoding);
+value.Encode(ref encoder);",
+    );
+
+    let encode_method = FunctionBuilder::new(&format!("{access} static"), "void", "Encode", FunctionType::BlockBody)
+        .add_comment(
+            "summary",
+            format!(r#"Encodes a <see cref="{escaped_identifier}" /> into a buffer writer."#),
+        )
+        .add_parameter(
+            "this global::System.Buffers.IBufferWriter<byte>",
+            "writer",
+            None,
+            Some("The buffer writer to encode the value into.".to_owned()),
+        )
+        .add_parameter(
+            &format!("in {escaped_identifier}"),
+            "value",
+            None,
+            Some("The value to encode.".to_owned()),
+        )
+        .add_parameter(
+            "SliceEncoding",
+            "encoding",
+            match encodings.as_slice() {
+                [encoding] => Some(*encoding),
+                _ => None,
+            },
+            Some("The encoding to encode the value with.".to_owned()),
+        )
+        .add_comment_with_attribute(
+            "exception",
+            "cref",
+            "global::System.NotSupportedException",
+            "Thrown if the struct doesn't support the provided encoding.",
+        )
+        .set_body(body)
+        .build();
+
+    let mut builder = ContainerBuilder::new(
+        &format!("{access} static class"),
+        &struct_def.escape_identifier_with_suffix("BufferWriterExtensions"),
+    );
+    builder
+        .add_comment(
+            "summary",
+            format!(r#"Provides an extension method for encoding a <see cref="{escaped_identifier}" /> into a buffer writer."#),
+        )
+        .add_generated_remark("static class", struct_def)
+        .add_generated_code_attribute()
+        .add_block(encode_method);
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::generate_buffer_writer_extension;
+    use crate::attribute_tests::compile_for_ast;
+    use slicec::grammar::Struct;
+
+    #[test]
+    fn buffer_writer_extension_encodes_into_the_writer() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::bufferWriterExtension]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_buffer_writer_extension(struct_def).to_string();
+
+        // Assert
+        assert!(code.contains("public static class MyStructBufferWriterExtensions"));
+        let expected = "\
+    public static void Encode(
+        this global::System.Buffers.IBufferWriter<byte> writer,
+        in MyStruct value,
+        SliceEncoding encoding = SliceEncoding.Slice2)
+    {
+        if (encoding != SliceEncoding.Slice2)
+        {
+            throw new global::System.NotSupportedException(
+                $\"The struct 'MyStruct' cannot be encoded with the {encoding} encoding.\");
+        }
+        var encoder = new SliceEncoder(writer, encoding);
+        value.Encode(ref encoder);
+    }";
+        assert!(code.contains(expected));
+    }
+}
@@ -4,13 +4,15 @@ use crate::builders::{
     AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionCallBuilder, FunctionType,
 };
 use crate::code_block::CodeBlock;
+use crate::cs_attributes::CsSealed;
+use crate::cs_options::CsOptions;
 use crate::decoding::decode_fields;
 use crate::encoding::encode_fields;
 use crate::member_util::*;
 use crate::slicec_ext::*;
-use slicec::grammar::{Class, Encoding, Field};
+use slicec::grammar::{AttributeFunctions, Class, Encoding, Field};
 
-pub fn generate_class(class_def: &Class) -> CodeBlock {
+pub fn generate_class(class_def: &Class, options: &CsOptions) -> CodeBlock {
     let class_name = class_def.escape_identifier();
     let namespace = class_def.namespace();
 
@@ -25,7 +27,11 @@ pub fn generate_class(class_def: &Class) -> CodeBlock {
     let mut non_nullable_base_fields = base_fields.clone();
     non_nullable_base_fields.retain(|f| !f.data_type.is_optional);
 
-    let mut class_builder = ContainerBuilder::new(&format!("{access} partial class"), &class_name);
+    let sealed = match class_def.has_attribute::<CsSealed>() {
+        true => " sealed",
+        false => "",
+    };
+    let mut class_builder = ContainerBuilder::new(&format!("{access}{sealed} partial class"), &class_name);
 
     if let Some(summary) = class_def.formatted_doc_comment_summary() {
         class_builder.add_comment("summary", summary);
@@ -36,7 +42,8 @@ pub fn generate_class(class_def: &Class) -> CodeBlock {
         .add_comments(class_def.formatted_doc_comment_seealso())
         .add_type_id_attribute(class_def)
         .add_compact_type_id_attribute(class_def)
-        .add_obsolete_attribute(class_def);
+        .add_obsolete_attribute(class_def)
+        .add_generated_code_attribute();
 
     if let Some(base) = class_def.base_class() {
         class_builder.add_base(base.escape_scoped_identifier(&namespace));
@@ -48,7 +55,7 @@ pub fn generate_class(class_def: &Class) -> CodeBlock {
     class_builder.add_block(
         fields
             .iter()
-            .map(|m| field_declaration(m))
+            .map(|m| field_declaration(m, options))
             .collect::<Vec<_>>()
             .join("\n\n")
             .into(),
@@ -205,3 +212,76 @@ fn encode_and_decode(class_def: &Class) -> CodeBlock {
 
     code
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attribute_tests::compile_for_ast;
+    use test_case::test_case;
+
+    #[test]
+    fn classes_with_compact_id_register_and_encode_it() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class MyClass(15) {}
+        ";
+        let ast = compile_for_ast(slice);
+        let class_def = ast.find_element::<Class>("Test::MyClass").unwrap();
+
+        // Act
+        let code = generate_class(class_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("[CompactSliceTypeId(15)]"));
+        assert!(code.contains(
+            "private static readonly int _compactSliceTypeId = typeof(MyClass).GetCompactSliceTypeId()!.Value;"
+        ));
+        assert!(code.contains("encoder.StartSlice(SliceTypeId, _compactSliceTypeId);"));
+    }
+
+    #[test]
+    fn classes_without_compact_id_only_use_their_type_id() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class MyClass {}
+        ";
+        let ast = compile_for_ast(slice);
+        let class_def = ast.find_element::<Class>("Test::MyClass").unwrap();
+
+        // Act
+        let code = generate_class(class_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(!code.contains("CompactSliceTypeId"));
+        assert!(code.contains("encoder.StartSlice(SliceTypeId);"));
+    }
+
+    #[test_case("", "public partial class"; "unsealed")]
+    #[test_case("[cs::sealed]", "public sealed partial class"; "sealed")]
+    fn classes_are_only_sealed_when_marked_with_sealed(attribute: &str, declaration: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            mode = Slice1
+            module Test
+
+            {attribute}
+            class MyClass {{}}
+            "
+        );
+        let ast = compile_for_ast(slice);
+        let class_def = ast.find_element::<Class>("Test::MyClass").unwrap();
+
+        // Act
+        let code = generate_class(class_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains(&format!("{declaration} MyClass : SliceClass")));
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
+use crate::code_block::CodeBlock;
+use crate::cs_attributes::{CsDecodeBytes, CsEncodeBytes, CsType};
+use crate::slicec_ext::EntityExt;
+use convert_case::Case;
+use slicec::grammar::*;
+
+/// Generates the encoder and decoder extension methods of a custom type marked with `cs::encodeBytes` and
+/// `cs::decodeBytes`. The generated code calls these extension methods to encode and decode custom types, like it does
+/// for the extension methods that users write themselves for other custom types. The wire form of these custom types
+/// is a size-prefixed sequence of bytes, which is fully owned by the user-provided converter methods.
+pub fn generate_custom_type(custom_type: &CustomType) -> CodeBlock {
+    let mut code = CodeBlock::default();
+    let (Some(encode_bytes), Some(decode_bytes)) = (
+        custom_type.find_attribute::<CsEncodeBytes>(),
+        custom_type.find_attribute::<CsDecodeBytes>(),
+    ) else {
+        return code;
+    };
+
+    code.add_block(encoder_extensions(custom_type, encode_bytes));
+    code.add_block(decoder_extensions(custom_type, decode_bytes));
+    code
+}
+
+fn encoder_extensions(custom_type: &CustomType, encode_bytes: &CsEncodeBytes) -> CodeBlock {
+    let access = custom_type.access_modifier();
+    let identifier = custom_type.cs_identifier(Case::Pascal);
+    let type_string = &custom_type.find_attribute::<CsType>().unwrap().type_string;
+
+    let mut builder = ContainerBuilder::new(
+        &format!("{access} static class"),
+        &custom_type.escape_identifier_with_suffix("SliceEncoderExtensions"),
+    );
+    builder
+        .add_comment(
+            "summary",
+            format!(r#"Provides extension methods for encoding a <see cref="{type_string}" /> as bytes using a <see cref="SliceEncoder" />."#),
+        )
+        .add_generated_remark("static class", custom_type)
+        .add_generated_code_attribute();
+
+    builder.add_block(
+        FunctionBuilder::new(
+            &format!("{access} static"),
+            "void",
+            &format!("Encode{identifier}"),
+            FunctionType::ExpressionBody,
+        )
+        .add_comment(
+            "summary",
+            format!(r#"Encodes a <see cref="{type_string}" /> as a sequence of bytes."#),
+        )
+        .add_parameter(
+            "this ref SliceEncoder",
+            "encoder",
+            None,
+            Some("The Slice encoder.".to_owned()),
+        )
+        .add_parameter(type_string, "value", None, Some("The value to encode.".to_owned()))
+        .set_body(format!("encoder.EncodeSequence({}(value))", encode_bytes.method).into())
+        .build(),
+    );
+
+    let mut nullable_body = CodeBlock::default();
+    writeln!(
+        nullable_body,
+        "\
+if (value is {type_string} notNull)
+{{
+    encoder.EncodeBool(true);
+    encoder.Encode{identifier}(notNull);
+}}
+else
+{{
+    encoder.EncodeBool(false);
+}}",
+    );
+    builder.add_block(
+        FunctionBuilder::new(
+            &format!("{access} static"),
+            "void",
+            &format!("EncodeNullable{identifier}"),
+            FunctionType::BlockBody,
+        )
+        .add_comment(
+            "summary",
+            format!(r#"Encodes a nullable <see cref="{type_string}" /> as a bool followed by a sequence of bytes if the value isn't null."#),
+        )
+        .add_parameter("this ref SliceEncoder", "encoder", None, Some("The Slice encoder.".to_owned()))
+        .add_parameter(
+            &format!("{type_string}?"),
+            "value",
+            None,
+            Some("The value to encode.".to_owned()),
+        )
+        .set_body(nullable_body)
+        .build(),
+    );
+
+    builder.build()
+}
+
+fn decoder_extensions(custom_type: &CustomType, decode_bytes: &CsDecodeBytes) -> CodeBlock {
+    let access = custom_type.access_modifier();
+    let identifier = custom_type.cs_identifier(Case::Pascal);
+    let type_string = &custom_type.find_attribute::<CsType>().unwrap().type_string;
+
+    let mut builder = ContainerBuilder::new(
+        &format!("{access} static class"),
+        &custom_type.escape_identifier_with_suffix("SliceDecoderExtensions"),
+    );
+    builder
+        .add_comment(
+            "summary",
+            format!(r#"Provides extension methods for decoding a <see cref="{type_string}" /> from bytes using a <see cref="SliceDecoder" />."#),
+        )
+        .add_generated_remark("static class", custom_type)
+        .add_generated_code_attribute();
+
+    builder.add_block(
+        FunctionBuilder::new(
+            &format!("{access} static"),
+            type_string,
+            &format!("Decode{identifier}"),
+            FunctionType::ExpressionBody,
+        )
+        .add_comment(
+            "summary",
+            format!(r#"Decodes a <see cref="{type_string}" /> from a sequence of bytes."#),
+        )
+        .add_parameter(
+            "this ref SliceDecoder",
+            "decoder",
+            None,
+            Some("The Slice decoder.".to_owned()),
+        )
+        .add_comment("returns", "The decoded value.")
+        .set_body(format!("{}(decoder.DecodeSequence<byte>())", decode_bytes.method).into())
+        .build(),
+    );
+
+    builder.add_block(
+        FunctionBuilder::new(
+            &format!("{access} static"),
+            &format!("{type_string}?"),
+            &format!("DecodeNullable{identifier}"),
+            FunctionType::ExpressionBody,
+        )
+        .add_comment(
+            "summary",
+            format!(r#"Decodes a nullable <see cref="{type_string}" /> from a bool followed by a sequence of bytes if the value isn't null."#),
+        )
+        .add_parameter("this ref SliceDecoder", "decoder", None, Some("The Slice decoder.".to_owned()))
+        .add_comment("returns", "The decoded value, or null.")
+        .set_body(format!("decoder.DecodeBool() ? decoder.Decode{identifier}() : null").into())
+        .build(),
+    );
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::generate_custom_type;
+    use crate::attribute_tests::compile_for_ast;
+    use slicec::grammar::CustomType;
+
+    #[test]
+    fn custom_types_encoded_as_bytes_call_the_converters() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::type(\"MyType\")]
+            [cs::encodeBytes(\"MyConverter.EncodeBytes\")]
+            [cs::decodeBytes(\"MyConverter.DecodeBytes\")]
+            custom MyCustom
+        ";
+        let ast = compile_for_ast(slice);
+        let custom_type = ast.find_element::<CustomType>("Test::MyCustom").unwrap();
+
+        // Act
+        let code = generate_custom_type(custom_type).to_string();
+
+        // Assert
+        assert!(code.contains("public static class MyCustomSliceEncoderExtensions"));
+        assert!(code.contains("public static class MyCustomSliceDecoderExtensions"));
+        assert!(code.contains(
+            "\
+    public static void EncodeMyCustom(
+        this ref SliceEncoder encoder,
+        MyType value) =>
+        encoder.EncodeSequence(MyConverter.EncodeBytes(value));"
+        ));
+        assert!(code.contains(
+            "\
+    public static MyType DecodeMyCustom(this ref SliceDecoder decoder) =>
+        MyConverter.DecodeBytes(decoder.DecodeSequence<byte>());"
+        ));
+        assert!(code.contains("if (value is MyType notNull)"));
+        assert!(code.contains("decoder.DecodeBool() ? decoder.DecodeMyCustom() : null;"));
+    }
+
+    #[test]
+    fn custom_types_without_converters_generate_nothing() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::type(\"MyType\")]
+            custom MyCustom
+        ";
+        let ast = compile_for_ast(slice);
+        let custom_type = ast.find_element::<CustomType>("Test::MyCustom").unwrap();
+
+        // Act
+        let code = generate_custom_type(custom_type);
+
+        // Assert
+        assert!(code.is_empty());
+    }
+}
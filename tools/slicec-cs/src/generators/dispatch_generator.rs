@@ -28,7 +28,8 @@ pub fn generate_dispatch(interface_def: &Interface) -> CodeBlock {
         )
         .add_comments(interface_def.formatted_doc_comment_seealso())
         .add_type_id_attribute(interface_def)
-        .add_default_service_path_attribute(interface_def);
+        .add_default_service_path_attribute(interface_def)
+        .add_generated_code_attribute();
 
     interface_builder.add_bases(
         &bases
@@ -319,10 +320,37 @@ fn operation_declaration(operation: &Operation) -> CodeBlock {
     if let Some(summary) = operation.formatted_doc_comment_summary() {
         builder.add_comment("summary", summary);
     }
-    builder
-        .add_operation_parameters(operation, TypeContext::IncomingParam)
-        .add_comments(operation.formatted_doc_comment_seealso())
-        .build()
+    if let Some(remarks) = operation.formatted_doc_comment_remarks() {
+        builder.add_comment("remarks", remarks);
+    }
+    builder.add_operation_parameters(operation, TypeContext::IncomingParam);
+    if operation.has_attribute::<CsEncodedReturn>() {
+        builder.add_comment("returns", encoded_return_comment(operation));
+    }
+    builder.add_comments(operation.formatted_doc_comment_seealso());
+    for attribute in operation.cs_attributes() {
+        builder.add_attribute(attribute);
+    }
+    builder.build()
+}
+
+fn encoded_return_comment(operation: &Operation) -> String {
+    let payload = format!(
+        r#"the return value payload encoded by <see cref="Response.Encode{}" />"#,
+        operation.escape_identifier(),
+    );
+    let payload = match operation.streamed_return_member() {
+        Some(stream_member) => format!(
+            "A value task holding {payload} and the <c>{}</c> stream",
+            stream_member.field_name()
+        ),
+        None => format!("A value task holding {payload}"),
+    };
+    format!(
+        r#"{payload}.
+This payload is sent as-is: it must be encoded with the encode options of the request's
+<see cref="ISliceFeature" />, which are the options the generated code uses to encode regular return values."#
+    )
 }
 
 fn operation_dispatch(operation: &Operation) -> CodeBlock {
@@ -551,3 +579,59 @@ fn payload_continuation(operation: &Operation, encoding: &str) -> CodeBlock {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attribute_tests::compile_for_ast;
+
+    #[test]
+    fn encoded_return_operations_return_a_pre_encoded_payload() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface MyService {
+                [cs::encodedReturn]
+                op() -> string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let interface_def = ast.find_element::<Interface>("Test::MyService").unwrap();
+
+        // Act
+        let code = generate_dispatch(interface_def).to_string();
+
+        // Assert
+        assert!(
+            code.contains("global::System.Threading.Tasks.ValueTask<global::System.IO.Pipelines.PipeReader> OpAsync(")
+        );
+        assert!(code.contains(
+            r#"/// <returns>A value task holding the return value payload encoded by <see cref="Response.EncodeOp" />."#
+        ));
+        assert!(code.contains("return new IceRpc.OutgoingResponse(request) { Payload = returnValue };"));
+        assert!(!code.contains("Payload = Response.EncodeOp("));
+    }
+
+    #[test]
+    fn value_task_operations_keep_their_value_task_service_signature() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface MyService {
+                [cs::valueTask]
+                op() -> int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let interface_def = ast.find_element::<Interface>("Test::MyService").unwrap();
+
+        // Act
+        let code = generate_dispatch(interface_def).to_string();
+
+        // Assert
+        assert!(code.contains("public global::System.Threading.Tasks.ValueTask<int> OpAsync("));
+        assert_eq!(code.matches("await target.OpAsync(").count(), 1);
+    }
+}
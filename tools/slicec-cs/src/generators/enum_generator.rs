@@ -3,6 +3,8 @@
 use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
 use crate::code_block::CodeBlock;
 use crate::comments::CommentTag;
+use crate::cs_attributes::{CsCaseInsensitive, CsFlags};
+use crate::cs_options::CsOptions;
 use crate::cs_util::CsCase;
 use crate::decoding::*;
 use crate::encoding::*;
@@ -10,16 +12,17 @@ use crate::slicec_ext::*;
 use convert_case::Case;
 use slicec::grammar::*;
 
-pub fn generate_enum(enum_def: &Enum) -> CodeBlock {
+pub fn generate_enum(enum_def: &Enum, options: &CsOptions) -> CodeBlock {
     let mut code = CodeBlock::default();
     code.add_block(enum_declaration(enum_def));
 
     if enum_def.is_mapped_to_cs_enum() {
-        code.add_block(enum_underlying_extensions(enum_def));
+        code.add_block(enum_underlying_extensions(enum_def, options));
+        code.add_block(enum_parser(enum_def));
     }
 
-    code.add_block(enum_encoder_extensions(enum_def));
-    code.add_block(enum_decoder_extensions(enum_def));
+    code.add_block(enum_encoder_extensions(enum_def, options));
+    code.add_block(enum_decoder_extensions(enum_def, options));
     code
 }
 
@@ -37,16 +40,23 @@ fn enum_declaration(enum_def: &Enum) -> CodeBlock {
             .add_generated_remark("enum", enum_def)
             .add_comments(enum_def.formatted_doc_comment_seealso())
             .add_obsolete_attribute(enum_def)
+            .add_generated_code_attribute()
             .add_base(enum_def.get_underlying_cs_type())
             .add_block(enumerators(enum_def));
 
+        if enum_def.has_attribute::<CsFlags>() {
+            builder.add_attribute("global::System.Flags");
+        }
+
         // Add cs::attribute
         for attribute in enum_def.cs_attributes() {
             builder.add_attribute(attribute);
         }
         builder.build()
     } else {
-        // Mapped to a Dunet discriminated union.
+        // Mapped to a Dunet discriminated union. Enums with fields are Slice's tagged unions: each enumerator is
+        // generated as a nested record deriving from the enum's abstract record, and Dunet generates the exhaustive
+        // `Match` and `Switch` methods, which take a function per enumerator.
 
         let mut builder = ContainerBuilder::new(
             &format!(
@@ -64,6 +74,7 @@ fn enum_declaration(enum_def: &Enum) -> CodeBlock {
             .add_generated_remark("discriminated union", enum_def)
             .add_comments(enum_def.formatted_doc_comment_seealso())
             .add_obsolete_attribute(enum_def)
+            .add_generated_code_attribute()
             .add_block(enumerators_as_nested_records(enum_def));
 
         // Add cs::attribute
@@ -91,6 +102,10 @@ fn enumerators(enum_def: &Enum) -> CodeBlock {
             declaration.writeln(&CommentTag::new("summary", summary));
         }
 
+        if let Some(remarks) = enumerator.formatted_doc_comment_remarks() {
+            declaration.writeln(&CommentTag::new("remarks", remarks));
+        }
+
         for comment_tag in enumerator.formatted_doc_comment_seealso() {
             declaration.writeln(&comment_tag);
         }
@@ -128,6 +143,9 @@ fn enumerators_as_nested_records(enum_def: &Enum) -> CodeBlock {
         if let Some(summary) = enumerator.formatted_doc_comment_summary() {
             builder.add_comment("summary", summary);
         }
+        if let Some(remarks) = enumerator.formatted_doc_comment_remarks() {
+            builder.add_comment("remarks", remarks);
+        }
         builder
             .add_comments(enumerator.formatted_doc_comment_seealso())
             .add_obsolete_attribute(enumerator)
@@ -223,7 +241,7 @@ fn enumerators_as_nested_records(enum_def: &Enum) -> CodeBlock {
     code
 }
 
-fn enum_underlying_extensions(enum_def: &Enum) -> CodeBlock {
+fn enum_underlying_extensions(enum_def: &Enum, options: &CsOptions) -> CodeBlock {
     let access = enum_def.access_modifier();
     let escaped_identifier = enum_def.escape_identifier();
     let namespace = &enum_def.namespace();
@@ -245,7 +263,8 @@ fn enum_underlying_extensions(enum_def: &Enum) -> CodeBlock {
             in_definite::get_a_or_an(&cs_type),
         ),
     )
-    .add_generated_remark("static class", enum_def);
+    .add_generated_remark("static class", enum_def)
+    .add_generated_code_attribute();
 
     // When the number of enumerators is smaller than the distance between the min and max
     // values, the values are not consecutive and we need to use a set to validate the value
@@ -329,12 +348,191 @@ enumerator."#
         );
     }
 
+    if options.aggressive_inlining && as_enum_block.has_trivial_body() {
+        as_enum_block.add_aggressive_inlining_attribute();
+    }
+
     builder.add_block(as_enum_block.build());
 
     builder.build()
 }
 
-fn enum_encoder_extensions(enum_def: &Enum) -> CodeBlock {
+fn enum_parser(enum_def: &Enum) -> CodeBlock {
+    let access = enum_def.access_modifier();
+    let escaped_identifier = enum_def.escape_identifier();
+    let case_insensitive = enum_def.has_attribute::<CsCaseInsensitive>();
+
+    let mut builder = ContainerBuilder::new(
+        &format!("{access} static class"),
+        &format!("{}Parser", enum_def.cs_identifier(Case::Pascal)),
+    );
+    builder
+        .add_comment(
+            "summary",
+            format!(r#"Provides methods for parsing the name of a <see cref="{escaped_identifier}" /> enumerator."#),
+        )
+        .add_generated_remark("static class", enum_def)
+        .add_generated_code_attribute();
+
+    let mut body = CodeBlock::default();
+    if case_insensitive {
+        for enumerator in enum_def.enumerators() {
+            body.add_block(format!(
+                "\
+if (global::System.MemoryExtensions.Equals(value, \"{name}\", global::System.StringComparison.OrdinalIgnoreCase))
+{{
+    result = {escaped_identifier}.{enumerator_name};
+    return true;
+}}",
+                name = enumerator.cs_identifier(Case::Pascal),
+                enumerator_name = enumerator.escape_identifier(),
+            ));
+        }
+        body.add_block("result = default;\nreturn false;");
+    } else {
+        let mut cases = CodeBlock::default();
+        for enumerator in enum_def.enumerators() {
+            writeln!(
+                cases,
+                "\
+case \"{name}\":
+    result = {escaped_identifier}.{enumerator_name};
+    return true;",
+                name = enumerator.cs_identifier(Case::Pascal),
+                enumerator_name = enumerator.escape_identifier(),
+            );
+        }
+        writeln!(
+            body,
+            "\
+switch (value)
+{{
+    {cases}
+    default:
+        result = default;
+        return false;
+}}",
+            cases = cases.indent(),
+        );
+    }
+
+    let mut try_parse_builder =
+        FunctionBuilder::new(&format!("{access} static"), "bool", "TryParse", FunctionType::BlockBody);
+    try_parse_builder
+        .add_comment(
+            "summary",
+            format!(
+                r#"Converts the name of an enumerator into the corresponding <see cref="{escaped_identifier}" /> enumerator, without allocating a string. {matching}"#,
+                matching = match case_insensitive {
+                    true => "The name is matched case-insensitively.",
+                    false => "The name is matched case-sensitively.",
+                },
+            ),
+        )
+        .add_parameter(
+            "global::System.ReadOnlySpan<char>",
+            "value",
+            None,
+            Some("The name of the enumerator.".to_owned()),
+        )
+        .add_parameter(
+            &format!("out {escaped_identifier}"),
+            "result",
+            None,
+            Some("When this method returns <see langword=\"true\" />, the parsed enumerator.".to_owned()),
+        )
+        .add_comment(
+            "returns",
+            "<see langword=\"true\" /> if <paramref name=\"value\" /> is the name of an enumerator; otherwise, <see langword=\"false\" />.",
+        )
+        .set_body(body);
+
+    builder.add_block(try_parse_builder.build());
+
+    // C# enums can't implement interfaces, so these methods only mirror the methods of `IParsable<T>`. The format
+    // provider is ignored, since enumerator names don't depend on the culture.
+    builder.add_block(
+        FunctionBuilder::new(
+            &format!("{access} static"),
+            &escaped_identifier,
+            "Parse",
+            FunctionType::ExpressionBody,
+        )
+        .add_comment(
+            "summary",
+            format!(r#"Converts the name of an enumerator into the corresponding <see cref="{escaped_identifier}" /> enumerator."#),
+        )
+        .add_parameter("string", "s", None, Some("The name of the enumerator.".to_owned()))
+        .add_parameter(
+            "global::System.IFormatProvider?",
+            "provider",
+            None,
+            Some("This parameter is ignored.".to_owned()),
+        )
+        .add_comment("returns", "The parsed enumerator.")
+        .add_comment_with_attribute(
+            "exception",
+            "cref",
+            "global::System.FormatException",
+            "Thrown when <paramref name=\"s\" /> is not the name of an enumerator.",
+        )
+        .set_body(
+            format!(
+                r#"
+TryParse(s, out {escaped_identifier} result) ?
+    result :
+    throw new global::System.FormatException($"'{{s}}' is not the name of a {escaped_identifier} enumerator.")"#,
+            )
+            .into(),
+        )
+        .build(),
+    );
+
+    builder.add_block(
+        FunctionBuilder::new(&format!("{access} static"), "bool", "TryParse", FunctionType::BlockBody)
+            .add_comment(
+                "summary",
+                format!(r#"Converts the name of an enumerator into the corresponding <see cref="{escaped_identifier}" /> enumerator."#),
+            )
+            .add_parameter(
+                "string?",
+                "s",
+                None,
+                Some("The name of the enumerator, or <see langword=\"null\" />.".to_owned()),
+            )
+            .add_parameter(
+                "global::System.IFormatProvider?",
+                "provider",
+                None,
+                Some("This parameter is ignored.".to_owned()),
+            )
+            .add_parameter(
+                &format!("out {escaped_identifier}"),
+                "result",
+                None,
+                Some("When this method returns <see langword=\"true\" />, the parsed enumerator.".to_owned()),
+            )
+            .add_comment(
+                "returns",
+                "<see langword=\"true\" /> if <paramref name=\"s\" /> is the name of an enumerator; otherwise, <see langword=\"false\" />.",
+            )
+            .set_body(
+                "\
+if (s is null)
+{
+    result = default;
+    return false;
+}
+return TryParse(global::System.MemoryExtensions.AsSpan(s), out result);"
+                    .into(),
+            )
+            .build(),
+    );
+
+    builder.build()
+}
+
+fn enum_encoder_extensions(enum_def: &Enum, options: &CsOptions) -> CodeBlock {
     let access = enum_def.access_modifier();
     let escaped_identifier = enum_def.escape_identifier();
     let cs_type = enum_def.get_underlying_cs_type();
@@ -347,7 +545,8 @@ fn enum_encoder_extensions(enum_def: &Enum) -> CodeBlock {
         "summary",
         format!(r#"Provides an extension method for encoding a <see cref="{escaped_identifier}" /> using a <see cref="SliceEncoder" />."#),
     )
-    .add_generated_remark("static class", enum_def);
+    .add_generated_remark("static class", enum_def)
+    .add_generated_code_attribute();
 
     builder.add_block({
         let mut encode_builder = FunctionBuilder::new(
@@ -392,13 +591,17 @@ fn enum_encoder_extensions(enum_def: &Enum) -> CodeBlock {
             encode_builder.set_body("value.Encode(ref encoder)".into());
         }
 
+        if options.aggressive_inlining && encode_builder.has_trivial_body() {
+            encode_builder.add_aggressive_inlining_attribute();
+        }
+
         encode_builder.build()
     });
 
     builder.build()
 }
 
-fn enum_decoder_extensions(enum_def: &Enum) -> CodeBlock {
+fn enum_decoder_extensions(enum_def: &Enum, options: &CsOptions) -> CodeBlock {
     let access = enum_def.access_modifier();
     let escaped_identifier = enum_def.escape_identifier();
     let cs_type = enum_def.get_underlying_cs_type();
@@ -411,7 +614,8 @@ fn enum_decoder_extensions(enum_def: &Enum) -> CodeBlock {
     builder.add_comment(
         "summary",
         format!(r#"Provides an extension method for decoding a <see cref="{escaped_identifier}" /> using a <see cref="SliceDecoder" />."#),
-    ).add_generated_remark("static class", enum_def);
+    ).add_generated_remark("static class", enum_def)
+    .add_generated_code_attribute();
 
     builder.add_block({
         let mut decode_builder = FunctionBuilder::new(
@@ -518,8 +722,262 @@ fn enum_decoder_extensions(enum_def: &Enum) -> CodeBlock {
             decode_builder.set_body(body);
         }
 
+        if options.aggressive_inlining && decode_builder.has_trivial_body() {
+            decode_builder.add_aggressive_inlining_attribute();
+        }
+
         decode_builder.build()
     });
 
     builder.build()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attribute_tests::{compile_for_ast, compile_without_errors};
+    use test_case::test_case;
+
+    #[test]
+    fn flags_attribute_is_emitted() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::flags]
+            enum Permissions : uint8 {
+                None = 0
+                Read = 1
+                Write = 2
+                Execute = 4
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::Permissions").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("[global::System.Flags]\npublic enum Permissions : byte"));
+    }
+
+    #[test_case("enum", r#"value is >= 1 and <= 3 ?
+            (MyEnum)value :
+            throw new global::System.IO.InvalidDataException($"Invalid enumerator value '{value}' for MyEnum.");"#; "checked")]
+    #[test_case("unchecked enum", "(MyEnum)value;"; "unchecked")]
+    fn decoding_validates_checked_enum_values(enum_kind: &str, expected_conversion: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            {enum_kind} MyEnum : int32 {{
+                A = 1
+                B = 2
+                C = 3
+            }}
+        "
+        );
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::MyEnum").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = format!("public static MyEnum AsMyEnum(this int value) =>\n        {expected_conversion}");
+        assert!(code.contains(&expected));
+    }
+
+    #[test]
+    fn deprecated_enumerators_are_obsolete_but_still_decoded() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum MyEnum : int32 {
+                A = 1
+                [deprecated(\"Use C instead.\")]
+                B = 2
+                C = 3
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::MyEnum").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert_eq!(code.matches("global::System.Obsolete").count(), 1);
+        assert!(code.contains("    [global::System.Obsolete(\"Use C instead.\")]\n    B = 2,"));
+        assert!(code.contains("value is >= 1 and <= 3 ?"));
+    }
+
+    #[test]
+    fn enumerator_fields_are_decoded_in_the_order_they_are_encoded() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum Shape {
+                Circle(radius: float64)
+                Rectangle(width: float64, height: int32)
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::Shape").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public partial record class Circle"));
+        assert!(code.contains("public partial record class Rectangle"));
+        assert!(code.contains("Shape.Circle.Discriminant => DecodeCircle(ref decoder),"));
+        assert!(code.contains("Shape.Rectangle.Discriminant => DecodeRectangle(ref decoder),"));
+
+        // Each enumerator encodes its discriminant, then its fields, then the tag end marker.
+        let rectangle = &code[code.find("public partial record class Rectangle").unwrap()..];
+        let encode_positions = [
+            "encoder.EncodeVarInt32(Discriminant);",
+            "encoder.EncodeFloat64(this.Width);",
+            "encoder.EncodeInt32(this.Height);",
+            "encoder.EncodeVarInt32(Slice2Definitions.TagEndMarker);",
+        ]
+        .map(|line| rectangle.find(line).unwrap());
+        assert!(encode_positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // The decode function reads the fields back in the same order, after the discriminant was read by the switch.
+        let decode_rectangle = &code[code.rfind("static Shape.Rectangle DecodeRectangle").unwrap()..];
+        let decode_positions = [
+            "decoder.DecodeFloat64()",
+            "decoder.DecodeInt32()",
+            "decoder.SkipTagged();",
+        ]
+        .map(|line| decode_rectangle.find(line).unwrap());
+        assert!(decode_positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test_case(
+        "",
+        "switch (value)\n        {\n            case \"A\":\n                result = MyEnum.A;\n                return true;";
+        "case_sensitive"
+    )]
+    #[test_case(
+        "[cs::caseInsensitive]",
+        "if (global::System.MemoryExtensions.Equals(value, \"A\", global::System.StringComparison.OrdinalIgnoreCase))\n        {\n            result = MyEnum.A;\n            return true;\n        }";
+        "case_insensitive"
+    )]
+    fn try_parse_matches_enumerator_names(attribute: &str, expected_match: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            {attribute}
+            enum MyEnum : uint8 {{ A, B }}
+        "
+        );
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::MyEnum").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public static class MyEnumParser"));
+        assert!(code.contains(
+            "public static bool TryParse(\n        global::System.ReadOnlySpan<char> value,\n        out MyEnum result)"
+        ));
+        assert!(code.contains(expected_match));
+        assert!(code.contains("result = default;"));
+    }
+
+    #[test]
+    fn parse_methods_mirror_parsable() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum MyEnum : uint8 { A, B }
+        ";
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::MyEnum").unwrap();
+
+        // Act
+        let code = enum_parser(enum_def).to_string();
+
+        // Assert
+        assert!(code.contains(
+            r#"
+    public static MyEnum Parse(
+        string s,
+        global::System.IFormatProvider? provider) =>
+        TryParse(s, out MyEnum result) ?
+            result :
+            throw new global::System.FormatException($"'{s}' is not the name of a MyEnum enumerator.");"#
+        ));
+        assert!(code.contains(
+            "
+    public static bool TryParse(
+        string? s,
+        global::System.IFormatProvider? provider,
+        out MyEnum result)
+    {
+        if (s is null)
+        {
+            result = default;
+            return false;
+        }
+        return TryParse(global::System.MemoryExtensions.AsSpan(s), out result);
+    }"
+        ));
+    }
+
+    #[test]
+    fn try_parse_is_not_generated_for_enums_with_fields() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum Shape {
+                Circle(radius: float64)
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let enum_def = ast.find_element::<Enum>("Test::Shape").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(!code.contains("TryParse"));
+    }
+
+    #[test]
+    fn aggressive_inlining_is_applied_to_trivial_encoding_methods() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum MyEnum : uint8 { A, B }
+        ";
+        let options = CsOptions {
+            aggressive_inlining: true,
+            ..CsOptions::default()
+        };
+        let compilation_state = compile_without_errors(&[slice], &options);
+        let enum_def = compilation_state.ast.find_element::<Enum>("Test::MyEnum").unwrap();
+
+        // Act
+        let code = generate_enum(enum_def, &options).to_string();
+
+        // Assert
+        let attribute = "[global::System.Runtime.CompilerServices.MethodImpl(global::System.Runtime.CompilerServices.MethodImplOptions.AggressiveInlining)]";
+        assert!(code.contains(&format!("{attribute}\n    public static MyEnum AsMyEnum(")));
+        assert!(code.contains(&format!("{attribute}\n    public static void EncodeMyEnum(")));
+        assert!(code.contains(&format!("{attribute}\n    public static MyEnum DecodeMyEnum(")));
+    }
+}
@@ -2,13 +2,15 @@
 
 use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
 use crate::code_block::CodeBlock;
+use crate::cs_attributes::CsSealed;
+use crate::cs_options::CsOptions;
 use crate::decoding::decode_fields;
 use crate::encoding::encode_fields;
 use crate::member_util::*;
 use crate::slicec_ext::*;
-use slicec::grammar::{Encoding, Exception, Member};
+use slicec::grammar::{AttributeFunctions, Encoding, Exception, Member};
 
-pub fn generate_exception(exception_def: &Exception) -> CodeBlock {
+pub fn generate_exception(exception_def: &Exception, options: &CsOptions) -> CodeBlock {
     let exception_name = exception_def.escape_identifier();
     let has_base = exception_def.base.is_some();
 
@@ -18,7 +20,12 @@ pub fn generate_exception(exception_def: &Exception) -> CodeBlock {
 
     let access = exception_def.access_modifier();
 
-    let mut exception_class_builder = ContainerBuilder::new(&format!("{access} partial class"), &exception_name);
+    let sealed = match exception_def.has_attribute::<CsSealed>() {
+        true => " sealed",
+        false => "",
+    };
+    let mut exception_class_builder =
+        ContainerBuilder::new(&format!("{access}{sealed} partial class"), &exception_name);
 
     if let Some(summary) = exception_def.formatted_doc_comment_summary() {
         exception_class_builder.add_comment("summary", summary);
@@ -27,7 +34,8 @@ pub fn generate_exception(exception_def: &Exception) -> CodeBlock {
         .add_generated_remark("class", exception_def)
         .add_comments(exception_def.formatted_doc_comment_seealso())
         .add_obsolete_attribute(exception_def)
-        .add_type_id_attribute(exception_def);
+        .add_type_id_attribute(exception_def)
+        .add_generated_code_attribute();
 
     if let Some(base) = exception_def.base_exception() {
         exception_class_builder.add_base(base.escape_scoped_identifier(namespace));
@@ -38,7 +46,7 @@ pub fn generate_exception(exception_def: &Exception) -> CodeBlock {
     exception_class_builder.add_block(
         fields
             .iter()
-            .map(|m| field_declaration(m))
+            .map(|m| field_declaration(m, options))
             .collect::<Vec<_>>()
             .join("\n\n")
             .into(),
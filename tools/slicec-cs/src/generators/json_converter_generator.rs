@@ -0,0 +1,198 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
+use crate::code_block::CodeBlock;
+use crate::slicec_ext::{EntityExt, FieldExt, MemberExt};
+use convert_case::Case;
+use slicec::grammar::*;
+
+/// Generates a `System.Text.Json` converter for the provided struct, which serializes the struct's fields as JSON
+/// properties named after their C# identifiers.
+pub fn generate_json_converter(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let converter_name = struct_def.escape_identifier_with_suffix("JsonConverter");
+
+    let mut builder = ContainerBuilder::new(
+        &format!("{} sealed partial class", struct_def.access_modifier()),
+        &converter_name,
+    );
+    builder
+        .add_comment(
+            "summary",
+            format!(r#"Converts <see cref="{escaped_identifier}" /> to and from JSON."#),
+        )
+        .add_generated_remark("class", struct_def)
+        .add_generated_code_attribute()
+        .add_base(format!(
+            "global::System.Text.Json.Serialization.JsonConverter<{escaped_identifier}>"
+        ))
+        .add_block(read_method(struct_def))
+        .add_block(write_method(struct_def));
+
+    builder.build()
+}
+
+fn read_method(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let namespace = struct_def.namespace();
+    let fields = struct_def.fields();
+
+    let mut body = CodeBlock::default();
+    writeln!(
+        body,
+        "\
+if (reader.TokenType != global::System.Text.Json.JsonTokenType.StartObject)
+{{
+    throw new global::System.Text.Json.JsonException(\"Expected the start of a JSON object.\");
+}}",
+    );
+    for field in &fields {
+        writeln!(
+            body,
+            "{} {} = default!;",
+            field.property_type_string(&namespace),
+            field.parameter_name_with_prefix(),
+        );
+    }
+
+    let mut cases = CodeBlock::default();
+    for field in &fields {
+        writeln!(
+            cases,
+            "\
+case \"{json_name}\":
+    {local} = global::System.Text.Json.JsonSerializer.Deserialize<{field_type}>(ref reader, options)!;
+    break;",
+            json_name = field.cs_identifier(Case::Pascal),
+            local = field.parameter_name_with_prefix(),
+            field_type = field.property_type_string(&namespace),
+        );
+    }
+    writeln!(
+        cases,
+        "\
+default:
+    reader.Skip();
+    break;",
+    );
+
+    let constructor_arguments = fields
+        .iter()
+        .map(|field| field.parameter_name_with_prefix())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    writeln!(
+        body,
+        "\
+while (reader.Read())
+{{
+    if (reader.TokenType == global::System.Text.Json.JsonTokenType.EndObject)
+    {{
+        return new {escaped_identifier}({constructor_arguments});
+    }}
+
+    string? propertyName = reader.GetString();
+    reader.Read();
+    switch (propertyName)
+    {{
+        {cases}
+    }}
+}}
+throw new global::System.Text.Json.JsonException(\"Expected the end of a JSON object.\");",
+        cases = cases.indent().indent(),
+    );
+
+    FunctionBuilder::new("public override", &escaped_identifier, "Read", FunctionType::BlockBody)
+        .set_inherit_doc(true)
+        .add_parameter("ref global::System.Text.Json.Utf8JsonReader", "reader", None, None)
+        .add_parameter("global::System.Type", "typeToConvert", None, None)
+        .add_parameter("global::System.Text.Json.JsonSerializerOptions", "options", None, None)
+        .set_body(body)
+        .build()
+}
+
+fn write_method(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "writer.WriteStartObject();");
+    for field in struct_def.fields() {
+        let write_property = format!(
+            "\
+writer.WritePropertyName(\"{json_name}\");
+global::System.Text.Json.JsonSerializer.Serialize(writer, value.{field_name}, options);",
+            json_name = field.cs_identifier(Case::Pascal),
+            field_name = field.field_name(),
+        );
+
+        // Tagged fields are written as optional JSON properties, which are omitted when the field isn't set.
+        if field.is_tagged() {
+            writeln!(
+                body,
+                "\
+if (value.{field_name} is not null)
+{{
+    {write_property}
+}}",
+                field_name = field.field_name(),
+                write_property = CodeBlock::from(write_property).indent(),
+            );
+        } else {
+            writeln!(body, "{write_property}");
+        }
+    }
+    writeln!(body, "writer.WriteEndObject();");
+
+    FunctionBuilder::new("public override", "void", "Write", FunctionType::BlockBody)
+        .set_inherit_doc(true)
+        .add_parameter("global::System.Text.Json.Utf8JsonWriter", "writer", None, None)
+        .add_parameter(&escaped_identifier, "value", None, None)
+        .add_parameter("global::System.Text.Json.JsonSerializerOptions", "options", None, None)
+        .set_body(body)
+        .build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attribute_tests::compile_for_ast;
+
+    #[test]
+    fn tagged_fields_are_optional_properties() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::json]
+            struct MyStruct {
+                a: int32
+                tag(1) b: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = write_method(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <inheritdoc/>
+public override void Write(
+    global::System.Text.Json.Utf8JsonWriter writer,
+    MyStruct value,
+    global::System.Text.Json.JsonSerializerOptions options)
+{
+    writer.WriteStartObject();
+    writer.WritePropertyName("A");
+    global::System.Text.Json.JsonSerializer.Serialize(writer, value.A, options);
+    if (value.B is not null)
+    {
+        writer.WritePropertyName("B");
+        global::System.Text.Json.JsonSerializer.Serialize(writer, value.B, options);
+    }
+    writer.WriteEndObject();
+}"#;
+        assert_eq!(code, expected);
+    }
+}
@@ -1,67 +1,153 @@
 // Copyright (c) ZeroC, Inc.
 
+mod buffer_writer_extension_generator;
 mod class_generator;
+mod custom_type_generator;
 mod dispatch_generator;
 mod enum_generator;
 mod exception_generator;
+mod json_converter_generator;
+mod pipe_reader_extension_generator;
 mod proxy_generator;
 mod struct_generator;
 
 use crate::code_block::CodeBlock;
-use crate::cs_options::CsOptions;
+use crate::cs_attributes::{CsBufferWriterExtension, CsIfDefined, CsJson, CsPipeReaderExtension, CsSealed};
+use crate::cs_options::{CsOptions, RpcProvider};
+use crate::cs_util::escape_string_literal;
 use crate::slicec_ext::ModuleExt;
 use slicec::grammar::*;
 use slicec::slice_file::SliceFile;
 use slicec::visitor::Visitor;
 
+/// Where the encoding and decoding members of structs are generated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Plumbing {
+    /// The plumbing is generated with the rest of each struct.
+    Included,
+    /// The plumbing is omitted, since it's generated in a separate file.
+    Omitted,
+    /// Only the plumbing is generated, in partial structs. Nothing else is generated.
+    Only,
+}
+
 struct Generator<'a> {
     code: &'a mut CodeBlock,
     for_interfaces: bool,
+    plumbing: Plumbing,
+    options: &'a CsOptions,
+}
+
+impl Generator<'_> {
+    /// Adds the code generated for an entity, wrapped in `#if` directives if the entity or its module are marked with
+    /// `cs::ifDefined`. The module's directive is the outermost one, and both are outside any doc comments.
+    fn add_guarded_block(&mut self, entity: &impl Entity, code: CodeBlock) {
+        let guards = [
+            entity.find_attribute::<CsIfDefined>(),
+            entity.get_module().find_attribute::<CsIfDefined>(),
+        ];
+
+        let mut code = code;
+        for guard in guards.into_iter().flatten() {
+            code = format!("#if {}\n{code}\n#endif", guard.symbol).into();
+        }
+        self.code.add_block(code);
+    }
 }
 
 impl Visitor for Generator<'_> {
     fn visit_struct(&mut self, struct_def: &Struct) {
         if !self.for_interfaces {
-            self.code.add_block(struct_generator::generate_struct(struct_def));
+            let mut code = CodeBlock::default();
+            code.add_block(match self.plumbing {
+                Plumbing::Included => struct_generator::generate_struct(struct_def, self.options),
+                Plumbing::Omitted => struct_generator::generate_split_struct(struct_def, self.options).0,
+                Plumbing::Only => {
+                    let plumbing = struct_generator::generate_split_struct(struct_def, self.options).1;
+                    self.add_guarded_block(struct_def, plumbing);
+                    return;
+                }
+            });
+            if struct_def.has_attribute::<CsJson>() {
+                code.add_block(json_converter_generator::generate_json_converter(struct_def));
+            }
+            if struct_def.has_attribute::<CsBufferWriterExtension>() {
+                code.add_block(buffer_writer_extension_generator::generate_buffer_writer_extension(
+                    struct_def,
+                ));
+            }
+            if struct_def.has_attribute::<CsPipeReaderExtension>() {
+                code.add_block(pipe_reader_extension_generator::generate_pipe_reader_extension(
+                    struct_def,
+                    self.options,
+                ));
+            }
+            self.add_guarded_block(struct_def, code);
         }
     }
 
     fn visit_class(&mut self, class_def: &Class) {
-        if !self.for_interfaces {
-            self.code.add_block(class_generator::generate_class(class_def));
+        if !self.for_interfaces && self.plumbing != Plumbing::Only {
+            self.add_guarded_block(class_def, class_generator::generate_class(class_def, self.options));
         }
     }
 
     fn visit_exception(&mut self, exception_def: &Exception) {
-        if !self.for_interfaces {
-            self.code
-                .add_block(exception_generator::generate_exception(exception_def));
+        if !self.for_interfaces && self.plumbing != Plumbing::Only {
+            let code = exception_generator::generate_exception(exception_def, self.options);
+            self.add_guarded_block(exception_def, code);
         }
     }
 
     fn visit_interface(&mut self, interface_def: &Interface) {
         if self.for_interfaces {
-            self.code.add_block(proxy_generator::generate_proxy(interface_def));
-            self.code
-                .add_block(dispatch_generator::generate_dispatch(interface_def));
+            let mut code = CodeBlock::default();
+            code.add_block(proxy_generator::generate_proxy(interface_def, self.options));
+            code.add_block(dispatch_generator::generate_dispatch(interface_def));
+            self.add_guarded_block(interface_def, code);
         }
     }
 
     fn visit_enum(&mut self, enum_def: &Enum) {
-        if !self.for_interfaces {
-            self.code.add_block(enum_generator::generate_enum(enum_def));
+        if !self.for_interfaces && self.plumbing != Plumbing::Only {
+            self.add_guarded_block(enum_def, enum_generator::generate_enum(enum_def, self.options));
+        }
+    }
+
+    fn visit_custom_type(&mut self, custom_type: &CustomType) {
+        if !self.for_interfaces && self.plumbing != Plumbing::Only {
+            let code = custom_type_generator::generate_custom_type(custom_type);
+            if !code.is_empty() {
+                self.add_guarded_block(custom_type, code);
+            }
         }
     }
 }
 
-pub fn generate_from_slice_file(slice_file: &SliceFile, for_interfaces: bool, _options: &CsOptions) -> String {
-    // Write the preamble at the top of the generated file.
-    let mut generated_code = preamble(slice_file);
+pub fn generate_from_slice_file(slice_file: &SliceFile, for_interfaces: bool, options: &CsOptions) -> String {
+    let plumbing = match options.split_generated {
+        true => Plumbing::Omitted,
+        false => Plumbing::Included,
+    };
+    generate_file(slice_file, for_interfaces, plumbing, options)
+}
+
+/// Generates the C# file holding the encoding and decoding members of the structs defined in the provided Slice file,
+/// for `--split-generated`. These members are generated in partial structs, which complete the structs generated by
+/// `generate_from_slice_file`.
+pub fn generate_plumbing_from_slice_file(slice_file: &SliceFile, options: &CsOptions) -> String {
+    generate_file(slice_file, false, Plumbing::Only, options)
+}
 
+fn generate_file(slice_file: &SliceFile, for_interfaces: bool, plumbing: Plumbing, options: &CsOptions) -> String {
+    // Write the preamble at the top of the generated file.
     let filename = &slice_file.filename;
+    let mut generated_code = preamble(&[filename], options);
 
     if for_interfaces {
         generated_code.add_block("using IceRpc.Slice;\nusing ZeroC.Slice;");
+    } else if plumbing == Plumbing::Only {
+        generated_code.add_block("using ZeroC.Slice;");
     } else {
         generated_code.add_block("using ZeroC.Slice;");
         generated_code.add_block(format!("[assembly:Slice(\"{filename}.slice\")]"));
@@ -74,24 +160,191 @@ pub fn generate_from_slice_file(slice_file: &SliceFile, for_interfaces: bool, _o
         generated_code.add_block(format!("namespace {namespace};"));
 
         // Then generate code for the user's slice definitions.
-        let mut generator = Generator {
-            code: &mut generated_code,
-            for_interfaces,
+        generated_code.add_block(generate_definitions(slice_file, for_interfaces, plumbing, options));
+    }
+
+    format_generated_file(&generated_code)
+}
+
+/// Generates a single C# file containing the code generated for all the provided Slice files.
+/// The using directives are written once at the top of the file, and the generated
+/// definitions are grouped into a namespace block per namespace, in the order the namespaces were first encountered.
+pub fn generate_single_file(slice_files: &[&SliceFile], options: &CsOptions) -> String {
+    let filenames = slice_files
+        .iter()
+        .map(|file| file.filename.as_str())
+        .collect::<Vec<_>>();
+    let mut generated_code = preamble(&filenames, options);
+
+    generated_code.add_block("using ZeroC.Slice;");
+    if options.rpc_provider == RpcProvider::IceRpc {
+        generated_code.add_block("using IceRpc.Slice;");
+    }
+
+    let mut assembly_attributes = filenames
+        .iter()
+        .map(|filename| format!("[assembly:Slice(\"{filename}.slice\")]"))
+        .collect::<Vec<_>>();
+    assembly_attributes.extend(internals_visible_to_attributes(options));
+    generated_code.add_block(assembly_attributes.join("\n"));
+
+    let mut namespaces: Vec<(String, CodeBlock)> = Vec::new();
+    for slice_file in slice_files {
+        let Some(module_ptr) = &slice_file.module else {
+            continue;
+        };
+        let namespace = module_ptr.borrow().as_namespace();
+        let index = match namespaces.iter().position(|(name, _)| name == &namespace) {
+            Some(index) => index,
+            None => {
+                namespaces.push((namespace, CodeBlock::default()));
+                namespaces.len() - 1
+            }
         };
-        slice_file.visit_with(&mut generator);
+
+        let code = &mut namespaces[index].1;
+        code.add_block(generate_definitions(slice_file, false, Plumbing::Included, options));
+        if options.rpc_provider == RpcProvider::IceRpc {
+            code.add_block(generate_definitions(slice_file, true, Plumbing::Included, options));
+        }
     }
 
-    // End the file with a trailing newline.
-    generated_code.to_string() + "\n"
+    for (namespace, code) in namespaces {
+        generated_code.add_block(format!(
+            "\
+namespace {namespace}
+{{
+    {code}
+}}",
+            code = code.indent(),
+        ));
+    }
+
+    format_generated_file(&generated_code)
+}
+
+/// Generates the C# file holding the assembly attributes that apply to all the generated code, instead of to the code
+/// generated for a single Slice file. Since these attributes can only be applied once per assembly, this file is only
+/// generated once, and `generate_single_file` writes them itself. Returns `None` if there are no such attributes.
+pub fn generate_assembly_info(slice_files: &[&SliceFile], options: &CsOptions) -> Option<String> {
+    let attributes = internals_visible_to_attributes(options);
+    if attributes.is_empty() {
+        return None;
+    }
+
+    let filenames = slice_files
+        .iter()
+        .map(|file| file.filename.as_str())
+        .collect::<Vec<_>>();
+    let mut generated_code = preamble(&filenames, options);
+    generated_code.add_block(attributes.join("\n"));
+    Some(format_generated_file(&generated_code))
+}
+
+/// Returns an `InternalsVisibleTo` assembly attribute for each friend assembly provided with `--internals-visible-to`.
+/// Assemblies provided more than once only get a single attribute.
+fn internals_visible_to_attributes(options: &CsOptions) -> Vec<String> {
+    let mut attributes = Vec::new();
+    for assembly in &options.internals_visible_to {
+        let attribute = format!(
+            r#"[assembly:global::System.Runtime.CompilerServices.InternalsVisibleTo("{}")]"#,
+            escape_string_literal(assembly),
+        );
+        if !attributes.contains(&attribute) {
+            attributes.push(attribute);
+        }
+    }
+    attributes
+}
+
+/// Generates the C# code for the definitions contained in the provided Slice file.
+/// If `for_interfaces` is true, only code for interfaces is generated, otherwise code for everything else is.
+/// `plumbing` controls whether the encoding and decoding members of structs are generated.
+fn generate_definitions(
+    slice_file: &SliceFile,
+    for_interfaces: bool,
+    plumbing: Plumbing,
+    options: &CsOptions,
+) -> CodeBlock {
+    let mut code = CodeBlock::default();
+    let mut generator = Generator {
+        code: &mut code,
+        for_interfaces,
+        plumbing,
+        options,
+    };
+    slice_file.visit_with(&mut generator);
+    code
 }
 
-fn preamble(slice_file: &SliceFile) -> CodeBlock {
+/// Removes any duplicate using directives from the provided code, and sorts the remaining ones, with `System`
+/// namespaces first, followed by all other namespaces in alphabetical order. The sorted directives are written where
+/// the first using directive was. Alias directives (`using X = Y;`), static directives, and global directives are
+/// left untouched.
+fn sort_using_directives(code: &str) -> String {
+    let is_sortable_using = |line: &str| {
+        line.starts_with("using ") && line.ends_with(';') && !line.contains('=') && !line.starts_with("using static ")
+    };
+
+    // Using directives can only appear before the first namespace declaration.
+    let lines = code.lines().collect::<Vec<_>>();
+    let end = lines
+        .iter()
+        .position(|line| line.starts_with("namespace "))
+        .unwrap_or(lines.len());
+
+    let mut usings = lines[..end]
+        .iter()
+        .copied()
+        .filter(|line| is_sortable_using(line))
+        .collect::<Vec<_>>();
+    usings.sort_by_key(|line| {
+        let namespace = line["using ".len()..].trim_end_matches(';').trim();
+        let is_system = namespace == "System" || namespace.starts_with("System.");
+        (!is_system, namespace.to_owned())
+    });
+    usings.dedup();
+
+    let mut sorted_lines = Vec::with_capacity(lines.len());
+    let mut has_written_usings = false;
+    for (index, line) in lines.into_iter().enumerate() {
+        if index < end && is_sortable_using(line) {
+            if !has_written_usings {
+                sorted_lines.append(&mut usings);
+                has_written_usings = true;
+            }
+        } else {
+            sorted_lines.push(line);
+        }
+    }
+
+    sorted_lines.join("\n")
+}
+
+/// Formats the code generated for a C# file, right before it's written. Every generated file goes through this
+/// function, so generators don't need to worry about the whitespace between the blocks they emit. It sorts the using
+/// directives, collapses consecutive empty lines into a single one, removes trailing whitespace from each line, and
+/// ends the file with a single newline.
+fn format_generated_file(generated_code: &CodeBlock) -> String {
+    let code = sort_using_directives(&generated_code.to_string());
+
+    // Converting the code into a `CodeBlock` string normalizes its whitespace, including any consecutive empty lines
+    // left behind by removed using directives.
+    CodeBlock::from(code).to_string() + "\n"
+}
+
+fn preamble(filenames: &[&str], options: &CsOptions) -> CodeBlock {
+    // Without a nullable context, the generated fields are annotated with nullability attributes instead, and the C#
+    // compiler warns about the '?' annotations of reference types.
+    let nullable_context = match options.emit_nullable_context_per_member {
+        true => "#pragma warning disable CS8632 // Nullable annotation outside of a nullable context",
+        false => "#nullable enable",
+    };
+
     format!(
-        r#"// <auto-generated/>
-// slicec-cs version: '{version}'
-// Generated from file: '{file}.slice'
+        r#"{header}
 
-#nullable enable
+{nullable_context}
 
 #pragma warning disable CS1591 // Missing XML Comment
 #pragma warning disable CS1573 // Parameter has no matching param tag in the XML comment
@@ -99,8 +352,329 @@ fn preamble(slice_file: &SliceFile) -> CodeBlock {
 #pragma warning disable CS0618 // Type or member is obsolete
 #pragma warning disable CS0619 // Type or member is obsolete
 "#,
-        version = env!("CARGO_PKG_VERSION"),
-        file = slice_file.filename,
+        header = header(filenames, options),
     )
     .into()
 }
+
+/// Returns the header comment to write at the top of generated files, which is either the default header, or the
+/// header provided with `--generated-file-header`. Custom headers are always marked as auto-generated, unless they
+/// already include an `<auto-generated>` tag themselves.
+fn header(filenames: &[&str], options: &CsOptions) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+
+    let Some(custom_header) = &options.generated_file_header else {
+        let files = filenames
+            .iter()
+            .map(|filename| format!("'{filename}.slice'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let label = if filenames.len() == 1 { "file" } else { "files" };
+        return format!(
+            "\
+// <auto-generated/>
+// slicec-cs version: '{version}'
+// Generated from {label}: {files}"
+        );
+    };
+
+    let mut lines = custom_header
+        .replace("{version}", version)
+        .replace("{file}", &filenames.join(", "))
+        .lines()
+        .map(|line| match line.starts_with("//") {
+            true => line.to_owned(),
+            false => format!("// {line}").trim_end().to_owned(),
+        })
+        .collect::<Vec<_>>();
+    if !custom_header.contains("<auto-generated") {
+        lines.insert(0, "// <auto-generated/>".to_owned());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        format_generated_file, generate_assembly_info, generate_from_slice_file, generate_plumbing_from_slice_file,
+        generate_single_file, header, preamble, sort_using_directives,
+    };
+    use crate::attribute_tests::compile_without_errors;
+    use crate::code_block::CodeBlock;
+    use crate::cs_options::CsOptions;
+    use test_case::test_case;
+
+    #[test]
+    fn custom_header_is_commented_and_marked_as_generated() {
+        // Arrange
+        let options = CsOptions {
+            generated_file_header: Some("Copyright (c) Acme\n\n// Generated from '{file}.slice'".to_owned()),
+            ..CsOptions::default()
+        };
+
+        // Act
+        let header = header(&["Foo"], &options);
+
+        // Assert
+        let expected = "\
+// <auto-generated/>
+// Copyright (c) Acme
+//
+// Generated from 'Foo.slice'";
+        assert_eq!(header, expected);
+    }
+
+    #[test_case(false, "#nullable enable", "CS8632"; "file_level")]
+    #[test_case(true, "#pragma warning disable CS8632", "#nullable"; "per_member")]
+    fn nullable_context_is_only_enabled_for_the_whole_file_by_default(
+        emit_nullable_context_per_member: bool,
+        expected: &str,
+        unexpected: &str,
+    ) {
+        // Arrange
+        let options = CsOptions {
+            emit_nullable_context_per_member,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = preamble(&["Foo"], &options).to_string();
+
+        // Assert
+        assert!(code.contains(expected));
+        assert!(!code.contains(unexpected));
+    }
+
+    #[test]
+    fn single_file_groups_definitions_by_namespace() {
+        // Arrange
+        let slices = [
+            "module Test\nstruct First {}",
+            "module Other\nstruct Second {}",
+            "module Test\nstruct Third {}",
+        ];
+        let options = CsOptions::default();
+        let compilation_state = compile_without_errors(&slices, &options);
+        let slice_files = compilation_state.files.iter().collect::<Vec<_>>();
+
+        // Act
+        let code = generate_single_file(&slice_files, &options);
+
+        // Assert
+        assert_eq!(code.matches("using ZeroC.Slice;").count(), 1);
+        assert_eq!(code.matches("[assembly:Slice(").count(), 3);
+        assert_eq!(code.matches("namespace Test\n{").count(), 1);
+        assert_eq!(code.matches("namespace Other\n{").count(), 1);
+
+        let test_namespace = code.find("namespace Test").unwrap();
+        let other_namespace = code.find("namespace Other").unwrap();
+        let third = code.find("partial record struct Third").unwrap();
+        assert!(test_namespace < third && third < other_namespace);
+    }
+
+    #[test]
+    fn internals_visible_to_attributes_are_generated_once() {
+        // Arrange
+        let slices = ["module Test\nstruct First {}", "module Test\nstruct Second {}"];
+        let options = CsOptions {
+            internals_visible_to: vec![
+                "Test.Tests".to_owned(),
+                "Other.Tests".to_owned(),
+                "Test.Tests".to_owned(),
+            ],
+            ..CsOptions::default()
+        };
+        let compilation_state = compile_without_errors(&slices, &options);
+        let slice_files = compilation_state.files.iter().collect::<Vec<_>>();
+
+        // Act
+        let assembly_info = generate_assembly_info(&slice_files, &options).unwrap();
+        let single_file = generate_single_file(&slice_files, &options);
+        let per_file = generate_from_slice_file(slice_files[0], false, &options);
+
+        // Assert
+        let attribute = r#"[assembly:global::System.Runtime.CompilerServices.InternalsVisibleTo("Test.Tests")]"#;
+        for code in [&assembly_info, &single_file] {
+            assert_eq!(code.matches(attribute).count(), 1);
+            assert_eq!(code.matches("InternalsVisibleTo(\"Other.Tests\")").count(), 1);
+        }
+        assert!(!per_file.contains("InternalsVisibleTo"));
+        assert!(generate_assembly_info(&slice_files, &CsOptions::default()).is_none());
+    }
+
+    #[test]
+    fn generated_files_have_normalized_whitespace() {
+        // Arrange
+        let lines = [
+            "using ZeroC.Slice;   ",
+            "",
+            "",
+            "",
+            "namespace Test;",
+            "\t",
+            "public partial record struct S",
+            "{",
+            "    public int A { get; set; }\t\t",
+            "}",
+            "",
+            "",
+        ];
+        let code = CodeBlock::from(lines.join("\n"));
+
+        // Act
+        let formatted_code = format_generated_file(&code);
+
+        // Assert
+        let expected = "\
+using ZeroC.Slice;
+
+namespace Test;
+
+public partial record struct S
+{
+    public int A { get; set; }
+}
+";
+        assert_eq!(formatted_code, expected);
+    }
+
+    #[test]
+    fn using_directives_are_sorted_and_deduplicated() {
+        // Arrange
+        let code = "\
+// <auto-generated/>
+
+using ZeroC.Slice;
+using System.Buffers;
+
+using Alias = System.Collections.Generic.List<int>;
+global using ZeroC.Slice;
+using IceRpc.Slice;
+using System;
+using ZeroC.Slice;
+
+namespace Test;";
+
+        // Act
+        let sorted_code = sort_using_directives(code);
+
+        // Assert
+        let expected = "\
+// <auto-generated/>
+
+using System;
+using System.Buffers;
+using IceRpc.Slice;
+using ZeroC.Slice;
+
+using Alias = System.Collections.Generic.List<int>;
+global using ZeroC.Slice;
+
+namespace Test;";
+        assert_eq!(sorted_code, expected);
+    }
+
+    #[test]
+    fn if_defined_guards_stack_outside_doc_comments() {
+        // Arrange
+        let slice = "
+            [cs::ifDefined(\"OUTER\")]
+            module Test
+
+            /// This is a struct.
+            [cs::ifDefined(\"INNER\")]
+            struct S {}
+
+            struct T {}
+        ";
+        let options = CsOptions::default();
+        let compilation_state = compile_without_errors(&[slice], &options);
+
+        // Act
+        let code = generate_from_slice_file(&compilation_state.files[0], false, &options);
+
+        // Assert
+        assert_eq!(code.matches("#if OUTER\n").count(), 2);
+        assert_eq!(code.matches("#if INNER\n").count(), 1);
+        assert_eq!(code.matches("#endif").count(), 3);
+        assert!(code.contains("#if OUTER\n#if INNER\n/// <summary>This is a struct.</summary>"));
+        assert!(code.contains("}\n#endif\n#endif"));
+    }
+
+    #[test]
+    fn split_generated_moves_struct_plumbing_into_a_partial_struct() {
+        // Arrange
+        let slice = "
+            module Test
+
+            /// This is a struct.
+            struct S {
+                a: int32
+            }
+
+            enum E { A }
+        ";
+        let options = CsOptions {
+            split_generated: true,
+            ..CsOptions::default()
+        };
+        let compilation_state = compile_without_errors(&[slice], &options);
+        let slice_file = &compilation_state.files[0];
+
+        // Act
+        let code = generate_from_slice_file(slice_file, false, &options);
+        let plumbing_code = generate_plumbing_from_slice_file(slice_file, &options);
+
+        // Assert
+        // Both files declare the same namespace and partial struct, but only the first one has the struct's
+        // attributes, its fields, and the other definitions of the Slice file.
+        for code in [&code, &plumbing_code] {
+            assert!(code.contains("using ZeroC.Slice;\n"));
+            assert!(code.contains("namespace Test;\n"));
+            assert!(code.contains("public partial record struct S\n{"));
+        }
+        assert!(code.contains("/// <summary>This is a struct.</summary>"));
+        assert!(code.contains("public int A { get; set; }"));
+        assert!(code.contains("public enum E"));
+        assert!(code.contains("[assembly:Slice("));
+        assert!(!code.contains("public S(ref SliceDecoder decoder)"));
+        assert!(!code.contains("public readonly void Encode(ref SliceEncoder encoder)"));
+
+        assert!(!plumbing_code.contains("[assembly:Slice("));
+        assert!(!plumbing_code.contains("/// <summary>This is a struct.</summary>"));
+        assert!(!plumbing_code.contains("public int A { get; set; }"));
+        assert!(!plumbing_code.contains("public enum E"));
+        assert!(plumbing_code.contains("public S(ref SliceDecoder decoder)"));
+        assert!(plumbing_code.contains("public readonly void Encode(ref SliceEncoder encoder)"));
+    }
+
+    #[test]
+    fn classes_and_exceptions_are_only_sealed_when_marked_with_sealed() {
+        // Arrange
+        let slice = "
+            mode = Slice1
+            module Test
+
+            class Base {}
+
+            [cs::sealed]
+            class Derived : Base {}
+
+            exception BaseException {}
+
+            [cs::sealed]
+            exception DerivedException : BaseException {}
+        ";
+        let options = CsOptions::default();
+        let compilation_state = compile_without_errors(&[slice], &options);
+
+        // Act
+        let code = generate_from_slice_file(&compilation_state.files[0], false, &options);
+
+        // Assert
+        assert!(code.contains("public partial class Base : SliceClass"));
+        assert!(code.contains("public partial class BaseException : SliceException"));
+        assert!(code.contains("public sealed partial class Derived : Base"));
+        assert!(code.contains("public sealed partial class DerivedException : BaseException"));
+    }
+}
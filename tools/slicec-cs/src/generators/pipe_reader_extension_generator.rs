@@ -0,0 +1,171 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::proxy_generator::configure_await;
+use super::struct_generator::{check_encoding_parameter, cs_encodings};
+use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
+use crate::code_block::CodeBlock;
+use crate::cs_options::CsOptions;
+use crate::slicec_ext::EntityExt;
+use slicec::grammar::*;
+
+/// Generates a static class with a `Decode{Name}Async` extension method for `PipeReader`, which decodes the provided
+/// struct from a pipe reader. Since the encoded size of a struct isn't known before it's decoded, this method decodes
+/// the buffered bytes, and reads more bytes whenever they don't hold the full struct.
+pub fn generate_pipe_reader_extension(struct_def: &Struct, options: &CsOptions) -> CodeBlock {
+    let access = struct_def.access_modifier();
+    let escaped_identifier = struct_def.escape_identifier();
+    let encodings = cs_encodings(struct_def);
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "{}", check_encoding_parameter(struct_def, &encodings));
+    writeln!(
+        body,
+        "\
+while (true)
+{{
+    global::System.IO.Pipelines.ReadResult readResult = await reader.ReadAsync(cancellationToken){configure_await};
+    if (TryDecode(readResult.Buffer, readResult.IsCompleted, out {escaped_identifier} value, out long consumed))
+    {{
+        reader.AdvanceTo(readResult.Buffer.GetPosition(consumed));
+        return value;
+    }}
+
+    // Mark all the buffered bytes as examined, so the next read waits for more bytes.
+    reader.AdvanceTo(readResult.Buffer.Start, readResult.Buffer.End);
+}}
+
+// Decoding fails with an InvalidDataException when the buffer doesn't hold the full struct. If the reader isn't
+// completed, more bytes may be coming, so we try again once they're read.
+bool TryDecode(
+    global::System.Buffers.ReadOnlySequence<byte> buffer,
+    bool isCompleted,
+    out {escaped_identifier} value,
+    out long consumed)
+{{
+    var decoder = new SliceDecoder(buffer, encoding);
+    try
+    {{
+        value = new {escaped_identifier}(ref decoder);
+    }}
+    catch (global::System.IO.InvalidDataException) when (!isCompleted)
+    {{
+        value = default!;
+        consumed = 0;
+        return false;
+    }}
+    consumed = decoder.Consumed;
+    return true;
+}}",
+        configure_await = configure_await(options),
+    );
+
+    let decode_method = FunctionBuilder::new(
+        &format!("{access} static async"),
+        &format!("global::System.Threading.Tasks.ValueTask<{escaped_identifier}>"),
+        &struct_def.escape_identifier_with_prefix_and_suffix("Decode", "Async"),
+        FunctionType::BlockBody,
+    )
+    .add_comment(
+        "summary",
+        format!(r#"Decodes a <see cref="{escaped_identifier}" /> from a pipe reader."#),
+    )
+    .add_parameter(
+        "this global::System.IO.Pipelines.PipeReader",
+        "reader",
+        None,
+        Some("The pipe reader to decode the value from.".to_owned()),
+    )
+    .add_parameter(
+        "SliceEncoding",
+        "encoding",
+        match encodings.as_slice() {
+            [encoding] => Some(*encoding),
+            _ => None,
+        },
+        Some("The encoding of the value.".to_owned()),
+    )
+    .add_parameter(
+        "global::System.Threading.CancellationToken",
+        "cancellationToken",
+        Some("default"),
+        Some("A cancellation token that receives the cancellation requests.".to_owned()),
+    )
+    .add_comment("returns", "The decoded value.")
+    .add_comment_with_attribute(
+        "exception",
+        "cref",
+        "global::System.NotSupportedException",
+        "Thrown if the struct doesn't support the provided encoding.",
+    )
+    .add_comment_with_attribute(
+        "exception",
+        "cref",
+        "global::System.IO.InvalidDataException",
+        "Thrown if the reader completes before a full value is read, or if the value is invalid.",
+    )
+    .add_comment(
+        "remarks",
+        "The reader is advanced past the decoded value. Since a truncated value can't be told apart from an invalid \
+value, invalid values are only reported once the reader completes.",
+    )
+    .set_body(body)
+    .build();
+
+    let mut builder = ContainerBuilder::new(
+        &format!("{access} static class"),
+        &struct_def.escape_identifier_with_suffix("PipeReaderExtensions"),
+    );
+    builder
+        .add_comment(
+            "summary",
+            format!(
+                r#"Provides an extension method for decoding a <see cref="{escaped_identifier}" /> from a pipe reader."#
+            ),
+        )
+        .add_generated_remark("static class", struct_def)
+        .add_generated_code_attribute()
+        .add_block(decode_method);
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::generate_pipe_reader_extension;
+    use crate::attribute_tests::compile_without_errors;
+    use crate::cs_options::CsOptions;
+    use slicec::grammar::Struct;
+
+    #[test]
+    fn pipe_reader_extension_reads_until_the_struct_is_decoded() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::pipeReaderExtension]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let options = CsOptions::default();
+        let compilation_state = compile_without_errors(&[slice], &options);
+        let struct_def = compilation_state.ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_pipe_reader_extension(struct_def, &options).to_string();
+
+        // Assert
+        assert!(code.contains("public static class MyStructPipeReaderExtensions"));
+        let expected = "\
+    public static async global::System.Threading.Tasks.ValueTask<MyStruct> DecodeMyStructAsync(
+        this global::System.IO.Pipelines.PipeReader reader,
+        SliceEncoding encoding = SliceEncoding.Slice2,
+        global::System.Threading.CancellationToken cancellationToken = default)
+    {";
+        assert!(code.contains(expected));
+        assert!(code.contains("await reader.ReadAsync(cancellationToken).ConfigureAwait(false);"));
+        assert!(code.contains("reader.AdvanceTo(readResult.Buffer.GetPosition(consumed));"));
+        assert!(code.contains("reader.AdvanceTo(readResult.Buffer.Start, readResult.Buffer.End);"));
+        assert!(code.contains("catch (global::System.IO.InvalidDataException) when (!isCompleted)"));
+    }
+}
@@ -5,6 +5,8 @@ use crate::builders::{
 };
 use crate::code_block::CodeBlock;
 use crate::code_gen_util::TypeContext;
+use crate::cs_attributes::CsValueTask;
+use crate::cs_options::CsOptions;
 use crate::decoding::*;
 use crate::encoding::*;
 use crate::member_util::*;
@@ -12,7 +14,7 @@ use crate::slicec_ext::*;
 use slicec::grammar::attributes::Oneway;
 use slicec::grammar::*;
 
-pub fn generate_proxy(interface_def: &Interface) -> CodeBlock {
+pub fn generate_proxy(interface_def: &Interface, options: &CsOptions) -> CodeBlock {
     let namespace = interface_def.namespace();
     let interface = interface_def.interface_name(); // IFoo
     let slice_interface = interface_def.module_scoped_identifier();
@@ -40,6 +42,7 @@ pub fn generate_proxy(interface_def: &Interface) -> CodeBlock {
             interface_def,
         )
         .add_comments(interface_def.formatted_doc_comment_seealso())
+        .add_generated_code_attribute()
         .add_bases(&interface_bases)
         .add_block(proxy_interface_operations(interface_def));
     code.add_block(proxy_interface_builder.build());
@@ -61,8 +64,9 @@ This remote service must implement Slice interface {slice_interface}."#
         )
         .add_generated_remark("record struct", interface_def)
         .add_type_id_attribute(interface_def)
+        .add_generated_code_attribute()
         .add_block(request_class(interface_def))
-        .add_block(response_class(interface_def))
+        .add_block(response_class(interface_def, options))
         .add_block(
             format!(
                 r#"
@@ -124,6 +128,7 @@ public static implicit operator {base_impl}({proxy_impl} proxy) =>
         &format!("{access} static class"),
         &format!("{proxy_impl}SliceEncoderExtensions"),
     );
+    proxy_encoder_builder.add_generated_code_attribute();
 
     if interface_def.supported_encodings().supports(Encoding::Slice1) {
         proxy_encoder_builder.add_comment(
@@ -176,6 +181,7 @@ Provides an extension method for <see cref="SliceEncoder" /> to encode a <see cr
         &format!("{access} static class"),
         &format!("{proxy_impl}SliceDecoderExtensions"),
     );
+    proxy_decoder_builder.add_generated_code_attribute();
 
     if interface_def.supported_encodings().supports(Encoding::Slice1) {
         proxy_decoder_builder.add_comment(
@@ -275,7 +281,7 @@ fn proxy_operation_impl(operation: &Operation) -> CodeBlock {
     let namespace = &operation.namespace();
     let operation_name = operation.escape_identifier();
     let async_operation_name = operation.escape_identifier_with_suffix("Async");
-    let return_task = operation.invocation_return_task("Task");
+    let return_task = operation.invocation_return_task(invocation_task_type(operation));
 
     let parameters = operation.non_streamed_parameters();
 
@@ -378,7 +384,12 @@ if ({features_parameter}?.Get<IceRpc.Features.ICompressFeature>() is null)
 
     invocation_builder.add_argument(format!("cancellationToken: {cancellation_token_parameter}"));
 
-    let invocation = invocation_builder.build();
+    // InvokeAsync returns a Task, which we wrap (without awaiting it) for operations that return a ValueTask. The Task
+    // is still allocated: cs::valueTask only changes the signature of the proxy methods.
+    let invocation = match operation.has_attribute::<CsValueTask>() {
+        true => format!("new({})", invocation_builder.build()).into(),
+        false => invocation_builder.build(),
+    };
 
     match body_type {
         FunctionType::ExpressionBody => body.writeln(&invocation),
@@ -393,7 +404,7 @@ if ({features_parameter}?.Get<IceRpc.Features.ICompressFeature>() is null)
 
 fn proxy_base_operation_impl(operation: &Operation, namespace: &str) -> CodeBlock {
     let async_name = operation.escape_identifier_with_suffix("Async");
-    let return_task = operation.invocation_return_task("Task");
+    let return_task = operation.invocation_return_task(invocation_task_type(operation));
     let mut operation_params = operation
         .parameters()
         .iter()
@@ -427,17 +438,23 @@ fn proxy_interface_operations(interface_def: &Interface) -> CodeBlock {
     for operation in operations {
         let mut builder = FunctionBuilder::new(
             "",
-            &operation.invocation_return_task("Task"),
+            &operation.invocation_return_task(invocation_task_type(operation)),
             &operation.escape_identifier_with_suffix("Async"),
             FunctionType::Declaration,
         );
         if let Some(summary) = operation.formatted_doc_comment_summary() {
             builder.add_comment("summary", summary);
         }
+        if let Some(remarks) = operation.formatted_doc_comment_remarks() {
+            builder.add_comment("remarks", remarks);
+        }
         builder
             .add_operation_parameters(operation, TypeContext::OutgoingParam)
             .add_comments(operation.formatted_doc_comment_seealso())
             .add_obsolete_attribute(operation);
+        for attribute in operation.cs_attributes() {
+            builder.add_attribute(attribute);
+        }
         code.add_block(builder.build());
     }
 
@@ -516,7 +533,7 @@ fn request_class(interface_def: &Interface) -> CodeBlock {
     class_builder.build()
 }
 
-fn response_class(interface_def: &Interface) -> CodeBlock {
+fn response_class(interface_def: &Interface, options: &CsOptions) -> CodeBlock {
     let mut operations = interface_def.operations();
     operations.retain(|o| {
         // We need to generate a method to decode the responses of any operations with return members or any Slice1
@@ -572,15 +589,16 @@ fn response_class(interface_def: &Interface) -> CodeBlock {
             None,
         );
 
-        builder.set_body(response_operation_body(operation));
+        builder.set_body(response_operation_body(operation, options));
 
         class_builder.add_block(builder.build());
     }
     class_builder.build()
 }
 
-fn response_operation_body(operation: &Operation) -> CodeBlock {
+fn response_operation_body(operation: &Operation, options: &CsOptions) -> CodeBlock {
     let mut code = CodeBlock::default();
+    let configure_await = configure_await(options);
     let namespace = &operation.namespace();
     let encoding = operation.encoding;
     let non_streamed_members = operation.non_streamed_return_members();
@@ -597,7 +615,7 @@ await response.DecodeVoidReturnValueAsync(
     {encoding},
     sender,
     defaultActivator: null,
-    cancellationToken).ConfigureAwait(false);
+    cancellationToken){configure_await};
 ",
                 encoding = encoding.to_cs_encoding(),
             );
@@ -611,7 +629,7 @@ var {return_value} = await response.DecodeReturnValueAsync(
     sender,
     {return_value_decode_fn},
     defaultActivator: null,
-    cancellationToken).ConfigureAwait(false);
+    cancellationToken){configure_await};
 ",
                 return_value = non_streamed_members.to_argument_tuple(),
                 encoding = encoding.to_cs_encoding(),
@@ -702,7 +720,7 @@ response.DecodeReturnValueAsync(
                         "\
 try
 {{
-    {return_await} {decode_response}.ConfigureAwait(false);
+    {return_await} {decode_response}{configure_await};
 }}
 catch {catch_expression}
 {{
@@ -718,3 +736,173 @@ catch {catch_expression}
         code
     }
 }
+
+/// Returns the type of task returned by the proxy methods of this operation.
+fn invocation_task_type(operation: &Operation) -> &'static str {
+    match operation.has_attribute::<CsValueTask>() {
+        true => "ValueTask",
+        false => "Task",
+    }
+}
+
+/// Returns the suffix appended to the tasks awaited by the generated proxy code.
+pub fn configure_await(options: &CsOptions) -> &'static str {
+    if options.continue_on_captured_context {
+        ""
+    } else {
+        ".ConfigureAwait(false)"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attribute_tests::{compile_for_ast, compile_without_errors};
+    use test_case::test_case;
+
+    #[test]
+    fn cs_attribute_is_emitted_on_operation_parameters() {
+        // Arrange
+        let slice = r#"
+            module Test
+
+            interface MyInterface {
+                op([cs::attribute("FromBody")] name: string, count: int32)
+            }
+        "#;
+        let ast = compile_for_ast(slice);
+        let interface_def = ast.find_element::<Interface>("Test::MyInterface").unwrap();
+
+        // Act
+        let code = generate_proxy(interface_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("[FromBody] string name,\n"));
+        assert!(!code.contains("[FromBody] int count"));
+    }
+
+    #[test]
+    fn operations_named_like_generated_members_do_not_collide() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface MyInterface {
+                encode()
+                dispose()
+                fromPath(path: string)
+                invoker() -> string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let interface_def = ast.find_element::<Interface>("Test::MyInterface").unwrap();
+
+        // Act
+        let code = generate_proxy(interface_def, &CsOptions::default()).to_string();
+
+        // Assert
+        // Operations are always mapped to methods with an 'Async' suffix, which no other generated member has.
+        for operation_name in ["EncodeAsync", "DisposeAsync", "FromPathAsync", "InvokerAsync"] {
+            assert!(code.contains(&format!(" {operation_name}(")));
+        }
+        assert!(code.contains("public static MyInterfaceProxy FromPath(string path) =>"));
+        assert!(code.contains("public required IceRpc.IInvoker Invoker { get; init; }"));
+    }
+
+    #[test]
+    fn operations_take_a_trailing_cancellation_token() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface MyInterface {
+                op(name: string) -> int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let interface_def = ast.find_element::<Interface>("Test::MyInterface").unwrap();
+
+        // Act
+        let code = generate_proxy(interface_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected_parameters = "\
+    string name,
+    IceRpc.Features.IFeatureCollection? features = null,
+    global::System.Threading.CancellationToken cancellationToken = default)";
+        // Both the proxy interface method and its implementation in the proxy struct have this signature.
+        assert_eq!(code.matches(expected_parameters).count(), 2);
+        assert!(code.contains(
+            r#"/// <param name="cancellationToken">A cancellation token that receives the cancellation requests.</param>"#
+        ));
+        assert!(code.contains("cancellationToken: cancellationToken"));
+    }
+
+    #[test_case(false, 3; "configure await")]
+    #[test_case(true, 0; "continue on captured context")]
+    fn awaited_calls_are_configured_unless_continuing_on_captured_context(
+        continue_on_captured_context: bool,
+        expected_count: usize,
+    ) {
+        // Arrange
+        let slice1 = "
+            mode = Slice1
+            module Test
+
+            interface MyInterface {
+                opVoid()
+                opValue() -> int32
+            }
+        ";
+        let slice2 = "
+            module Test
+
+            interface MyStreamer {
+                opStream() -> (value: int32, stream data: string)
+            }
+        ";
+        let options = CsOptions {
+            continue_on_captured_context,
+            ..CsOptions::default()
+        };
+        let compilation_state = compile_without_errors(&[slice1, slice2], &options);
+
+        // Act
+        let code = ["Test::MyInterface", "Test::MyStreamer"]
+            .iter()
+            .map(|identifier| {
+                let interface_def = compilation_state.ast.find_element::<Interface>(identifier).unwrap();
+                generate_proxy(interface_def, &options).to_string()
+            })
+            .collect::<String>();
+
+        // Assert
+        assert_eq!(code.matches("await ").count(), 3);
+        assert_eq!(code.matches(".ConfigureAwait(false)").count(), expected_count);
+    }
+
+    #[test]
+    fn value_task_operations_wrap_the_invocation_task() {
+        // Arrange
+        let slice = "
+            module Test
+
+            interface MyInterface {
+                [cs::valueTask]
+                op() -> int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let interface_def = ast.find_element::<Interface>("Test::MyInterface").unwrap();
+
+        // Act
+        let code = generate_proxy(interface_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let signature = "global::System.Threading.Tasks.ValueTask<int> OpAsync(";
+        // Both the proxy interface method and its implementation in the proxy struct return a ValueTask.
+        assert_eq!(code.matches(signature).count(), 2);
+        assert!(!code.contains("global::System.Threading.Tasks.Task<int>"));
+        assert!(code.contains("=>\n        new(this.InvokeAsync(\n"));
+    }
+}
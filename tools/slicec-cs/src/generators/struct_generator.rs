@@ -1,39 +1,157 @@
 // Copyright (c) ZeroC, Inc.
 
-use crate::builders::{AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionType};
+use crate::builders::{
+    AttributeBuilder, Builder, CommentBuilder, ContainerBuilder, FunctionBuilder, FunctionCallBuilder, FunctionType,
+};
 use crate::code_block::CodeBlock;
-use crate::cs_attributes::CsReadonly;
+use crate::code_gen_util::get_bit_sequence_size;
+use crate::cs_attributes::{
+    CsBuilder, CsClass, CsComparable, CsCustomDefault, CsDataContract, CsDeconstruct, CsDeepClone, CsDelegates,
+    CsEncodeOptions, CsEncodeOrder, CsEncodeToArray, CsEncodedSize, CsFastEquals, CsFieldTags, CsFormattable, CsFramed,
+    CsJson, CsLifecycleHooks, CsMutable, CsNumeric, CsReadonly, CsStableHash, CsStructLayout, CsToString, CsTryDecode,
+    CsTryEncode, CsType, CsValidate, CsValueEqualitySkipTagged,
+};
+use crate::cs_options::CsOptions;
 use crate::decoding::*;
 use crate::encoding::*;
 use crate::member_util::*;
-use crate::slicec_ext::{CommentExt, EntityExt, MemberExt, TypeRefExt};
+use crate::slicec_ext::{CommentExt, EncodingExt, EntityExt, FieldExt, MemberExt, TypeRefExt};
+use convert_case::Case;
 use slicec::grammar::*;
 use slicec::supported_encodings::SupportedEncodings;
 
-pub fn generate_struct(struct_def: &Struct) -> CodeBlock {
+pub fn generate_struct(struct_def: &Struct, options: &CsOptions) -> CodeBlock {
+    build_struct(struct_def, options, false).0
+}
+
+/// Generates a struct as two parts of a partial type: the first part holds its fields and all the members unrelated to
+/// encoding, and the second part holds the encoding and decoding plumbing. Only the first part has the struct's doc
+/// comment, attributes and bases, since C# merges these across parts.
+pub fn generate_split_struct(struct_def: &Struct, options: &CsOptions) -> (CodeBlock, CodeBlock) {
+    let (code, plumbing) = build_struct(struct_def, options, true);
+    (code, plumbing.unwrap())
+}
+
+fn build_struct(struct_def: &Struct, options: &CsOptions, split: bool) -> (CodeBlock, Option<CodeBlock>) {
     let escaped_identifier = struct_def.escape_identifier();
     let fields = struct_def.fields();
     let namespace = struct_def.namespace();
+    let has_required_members = fields.iter().any(|field| is_required_member(field, options));
+    let has_validate = struct_def.has_attribute::<CsValidate>();
+    let has_lifecycle_hooks = struct_def.has_attribute::<CsLifecycleHooks>();
+    let is_framed = struct_def.has_attribute::<CsFramed>();
+    let encoded_fields = CsEncodeOrder::encoded_fields(struct_def);
+    let is_reordered = fields.iter().any(|field| field.has_attribute::<CsEncodeOrder>());
+
+    let is_class = struct_def.has_attribute::<CsClass>();
+
+    // Structs are also generated as readonly if all of their fields are readonly, unless they're marked `cs::mutable`.
+    let is_readonly_inferred = !is_class
+        && !struct_def.has_attribute::<CsReadonly>()
+        && !struct_def.has_attribute::<CsMutable>()
+        && !fields.is_empty()
+        && fields.iter().all(|field| field.is_cs_readonly() || field.is_cs_init());
 
     let mut declaration = vec![struct_def.access_modifier()];
-    if struct_def.has_attribute::<CsReadonly>() {
+    if struct_def.has_attribute::<CsReadonly>() || is_readonly_inferred {
         declaration.push("readonly");
     }
-    declaration.extend(["partial", "record", "struct"]);
+    // Structs marked with `cs::class` are generated as sealed record classes, to avoid copying large structs by value.
+    // With `--explicit-constructors-only`, they're generated as plain structs and classes instead of records.
+    let is_plain = options.explicit_constructors_only;
+    let record_kind = match (is_plain, is_class) {
+        (false, true) => "record class",
+        (false, false) => "record struct",
+        (true, true) => "class",
+        (true, false) => "struct",
+    };
+    if is_class {
+        declaration.push("sealed");
+    }
+    declaration.extend(["partial", record_kind]);
 
     let mut builder = ContainerBuilder::new(&declaration.join(" "), &escaped_identifier);
+    let mut plumbing_builder = split.then(|| ContainerBuilder::new(&declaration.join(" "), &escaped_identifier));
     if let Some(summary) = struct_def.formatted_doc_comment_summary() {
         builder.add_comment("summary", summary);
     }
+    if is_readonly_inferred {
+        builder.add_generated_remark_with_note(
+            record_kind,
+            "It's generated as a readonly struct because all of its fields are readonly.",
+            struct_def,
+        );
+    } else {
+        builder.add_generated_remark(record_kind, struct_def);
+    }
     builder
-        .add_generated_remark("record struct", struct_def)
         .add_comments(struct_def.formatted_doc_comment_seealso())
-        .add_obsolete_attribute(struct_def);
+        .add_obsolete_attribute(struct_def)
+        .add_generated_code_attribute();
+
+    if struct_def.has_attribute::<CsDataContract>() {
+        builder.add_attribute("global::System.Runtime.Serialization.DataContract");
+    }
+
+    if struct_def.has_attribute::<CsJson>() {
+        builder.add_attribute(format!(
+            "global::System.Text.Json.Serialization.JsonConverter(typeof({}))",
+            struct_def.escape_identifier_with_suffix("JsonConverter"),
+        ));
+    }
+
+    for attribute in struct_def.cs_attributes() {
+        builder.add_attribute(attribute);
+    }
+
+    if options.debugger_display {
+        if let Some(display) = debugger_display(&fields) {
+            builder.add_attribute(format!(r#"global::System.Diagnostics.DebuggerDisplay("{display}")"#));
+        }
+    }
+
+    if let Some(struct_layout) = struct_def.find_attribute::<CsStructLayout>() {
+        builder.add_attribute(format!(
+            "global::System.Runtime.InteropServices.StructLayout(global::System.Runtime.InteropServices.LayoutKind.{})",
+            struct_layout.layout_kind,
+        ));
+    }
+
+    // Records implement `IEquatable<T>` implicitly, but plain structs and classes must declare it.
+    if is_plain {
+        builder.add_base(format!("global::System.IEquatable<{escaped_identifier}>"));
+    }
+
+    if struct_def.has_attribute::<CsComparable>() {
+        builder.add_base(format!("global::System.IComparable<{escaped_identifier}>"));
+    }
+
+    // `ISpanFormattable` extends `IFormattable`, and is implemented by all the numeric types formattable structs wrap.
+    if struct_def.has_attribute::<CsFormattable>() {
+        builder.add_base("global::System.ISpanFormattable".to_owned());
+        if parsable_field(struct_def, options).is_some() {
+            builder.add_base(format!("global::System.IParsable<{escaped_identifier}>"));
+        }
+    }
+
+    // The generic math interfaces were introduced in .NET 7, but the operators themselves are always generated.
+    if struct_def.has_attribute::<CsNumeric>() && options.target_framework.supports_modern_features() {
+        for interface in [
+            "IAdditionOperators",
+            "ISubtractionOperators",
+            "IMultiplyOperators",
+            "IDivisionOperators",
+        ] {
+            builder.add_base(format!(
+                "global::System.Numerics.{interface}<{escaped_identifier}, {escaped_identifier}, {escaped_identifier}>"
+            ));
+        }
+    }
 
     builder.add_block(
         fields
             .iter()
-            .map(|m| field_declaration(m))
+            .map(|m| field_declaration(m, options))
             .collect::<Vec<_>>()
             .join("\n\n")
             .into(),
@@ -49,117 +167,3915 @@ pub fn generate_struct(struct_def: &Struct) -> CodeBlock {
         "summary",
         format!(r#"Constructs a new instance of <see cref="{escaped_identifier}" />."#),
     );
+    if has_required_members {
+        main_constructor.add_sets_required_members_attribute();
+    }
 
-    for field in &fields {
+    for (field, default_value) in fields.iter().zip(field_default_values(&fields)) {
         main_constructor.add_parameter(
-            &field.data_type().field_type_string(&namespace),
+            &constructor_parameter_type(field, &namespace),
             field.parameter_name().as_str(),
-            None,
+            default_value,
             field.formatted_doc_comment_summary(),
         );
     }
     main_constructor.set_body({
         let mut code = CodeBlock::default();
         for field in &fields {
-            writeln!(code, "this.{} = {};", field.field_name(), field.parameter_name(),);
+            match field.is_cs_readonly_list() {
+                true => writeln!(
+                    code,
+                    "this.{} = global::System.Linq.Enumerable.ToArray({});",
+                    field.backing_field_name(),
+                    field.parameter_name(),
+                ),
+                false => writeln!(code, "this.{} = {};", field.field_name(), field.parameter_name(),),
+            }
+        }
+        if has_validate {
+            writeln!(code, "Validate();");
         }
         code
     });
     builder.add_block(main_constructor.build());
 
+    if struct_def.has_attribute::<CsFieldTags>() {
+        builder.add_block(field_tags(struct_def, options));
+    }
+
+    if let Some(custom_default) = struct_def.find_attribute::<CsCustomDefault>() {
+        builder.add_block(custom_default_field(struct_def, custom_default));
+    }
+
     // Decode constructor
-    let mut decode_body = generate_encoding_blocks(&fields, struct_def.supported_encodings(), decode_fields, "decoder");
+    // When possible, the fields are decoded directly into the arguments of the main constructor, which assigns them
+    // and validates the struct. C# evaluates these arguments in order, so the fields are decoded in the right order.
+    // Framed structs must decode their length prefix first, and reordered structs must decode their fields in their
+    // encode order, so both always decode their fields in the body.
+    let chained_arguments = chained_decode_arguments(struct_def).filter(|_| !is_framed && !is_reordered);
+    let mut decode_body = match chained_arguments {
+        Some(_) => CodeBlock::default(),
+        None => generate_encoding_blocks(
+            &encoded_fields,
+            struct_def.supported_encodings(),
+            decode_field_blocks,
+            "decoder",
+        ),
+    };
 
     if !struct_def.is_compact {
         writeln!(decode_body, "decoder.SkipTagged();");
     }
-    builder.add_block(
-            FunctionBuilder::new(
-                struct_def.access_modifier(),
-                "",
-                &escaped_identifier,
-                FunctionType::BlockBody,
-            )
-            .add_comment(
-                "summary",
-                format!(r#"Constructs a new instance of <see cref="{escaped_identifier}" /> and decodes its fields from a Slice decoder."#),
-            )
-            .add_parameter(
-                "ref SliceDecoder",
-                "decoder",
-                None,
-                Some("The Slice decoder.".to_owned()),
-            )
-            .set_body(decode_body)
-            .build(),
+    if is_framed {
+        decode_body = decode_frame(struct_def, decode_body);
+    }
+    if has_validate && chained_arguments.is_none() {
+        writeln!(decode_body, "Validate();");
+    }
+    if has_lifecycle_hooks {
+        writeln!(decode_body, "OnDecoded();");
+    }
+    let mut decode_constructor = FunctionBuilder::new(
+        struct_def.access_modifier(),
+        "",
+        &escaped_identifier,
+        FunctionType::BlockBody,
+    );
+    decode_constructor
+        .add_comment(
+            "summary",
+            format!(r#"Constructs a new instance of <see cref="{escaped_identifier}" /> and decodes its fields from a Slice decoder."#),
+        )
+        .add_parameter(
+            "ref SliceDecoder",
+            "decoder",
+            None,
+            Some("The Slice decoder.".to_owned()),
+        )
+        .set_body(decode_body);
+    if let Some(arguments) = &chained_arguments {
+        decode_constructor.add_this_arguments(arguments);
+    }
+    if has_required_members {
+        decode_constructor.add_sets_required_members_attribute();
+    }
+    if options.hide_generated_plumbing {
+        decode_constructor
+            .add_compiler_generated_attribute()
+            .add_never_editor_browsable_attribute();
+    }
+    add_skip_locals_init(&mut decode_constructor, options);
+    plumbing_target(&mut builder, &mut plumbing_builder).add_block(decode_constructor.build());
+
+    // Record structs are copied by value, but record classes are copied by their copy constructor, which `with`
+    // expressions call. We replace the synthesized copy constructor so that copies are validated too.
+    if has_validate && is_class && !is_plain {
+        builder.add_block(copy_constructor(struct_def, options));
+    }
+
+    if struct_def.has_attribute::<CsTryDecode>() {
+        plumbing_target(&mut builder, &mut plumbing_builder).add_block(try_decode(struct_def));
+    }
+
+    if has_validate {
+        builder.add_block(
+            FunctionBuilder::new("partial", "void", "Validate", FunctionType::Declaration)
+                .add_comment("summary", "Validates the fields of this struct.")
+                .add_comment(
+                    "remarks",
+                    "This method is called at the end of each constructor, including the copy constructor of record classes. Implement it to check the struct's invariants.",
+                )
+                .build(),
         );
+    }
+
+    if has_lifecycle_hooks {
+        builder.add_block(lifecycle_hooks(struct_def));
+    }
 
     // Encode method
-    let mut encode_body = generate_encoding_blocks(&fields, struct_def.supported_encodings(), encode_fields, "encoder");
+    let mut encode_body = CodeBlock::default();
+    if has_lifecycle_hooks {
+        writeln!(encode_body, "OnEncoding(ref encoder);");
+    }
+    encode_body.write(&generate_encoding_blocks(
+        &encoded_fields,
+        struct_def.supported_encodings(),
+        encode_field_blocks,
+        "encoder",
+    ));
 
     if !struct_def.is_compact {
         writeln!(encode_body, "encoder.EncodeVarInt32(Slice2Definitions.TagEndMarker);");
     }
-    builder.add_block(
-        FunctionBuilder::new(
-            &(struct_def.access_modifier().to_owned() + " readonly"),
-            "void",
-            "Encode",
-            FunctionType::BlockBody,
-        )
+    if is_framed {
+        encode_body = encode_frame(encode_body);
+    }
+
+    let has_encode_options = struct_def.has_attribute::<CsEncodeOptions>();
+    let mut encode_method = FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "void",
+        "Encode",
+        match has_encode_options {
+            true => FunctionType::ExpressionBody,
+            false => FunctionType::BlockBody,
+        },
+    );
+    encode_method
         .add_comment("summary", "Encodes the fields of this struct with a Slice encoder.")
         .add_parameter(
             "ref SliceEncoder",
             "encoder",
             None,
             Some("The Slice encoder.".to_owned()),
-        )
-        .set_body(encode_body)
-        .build(),
-    );
+        );
+    if options.hide_generated_plumbing {
+        encode_method
+            .add_compiler_generated_attribute()
+            .add_never_editor_browsable_attribute();
+    }
 
-    builder.build()
+    let encode_with_options_method = if has_encode_options {
+        // The default overload forwards to the overload with encode options, which is the only one that encodes.
+        encode_method.set_body("Encode(ref encoder, maxEncodedByteCount: -1)".into());
+        Some(encode_with_options(struct_def, encode_body))
+    } else {
+        encode_method.set_body(encode_body);
+        None
+    };
+    if options.aggressive_inlining && encode_method.has_trivial_body() {
+        encode_method.add_aggressive_inlining_attribute();
+    }
+    add_skip_locals_init(&mut encode_method, options);
+    plumbing_target(&mut builder, &mut plumbing_builder).add_block(encode_method.build());
+    if let Some(encode_with_options_method) = encode_with_options_method {
+        plumbing_target(&mut builder, &mut plumbing_builder).add_block(encode_with_options_method);
+    }
+
+    if struct_def.has_attribute::<CsEncodeToArray>() {
+        plumbing_target(&mut builder, &mut plumbing_builder).add_block(encode_to_array(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsTryEncode>() {
+        let target = plumbing_target(&mut builder, &mut plumbing_builder);
+        target.add_block(try_encode(struct_def));
+        target.add_block(try_encode_buffer_writer());
+    }
+
+    if struct_def.has_attribute::<CsDelegates>() {
+        plumbing_target(&mut builder, &mut plumbing_builder).add_block(delegates(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsEncodedSize>() {
+        plumbing_target(&mut builder, &mut plumbing_builder).add_block(encoded_size(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsDeconstruct>() {
+        builder.add_block(deconstruct(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsDeepClone>() {
+        builder.add_block(deep_clone(struct_def));
+    }
+
+    // Plain structs and classes don't get the `ToString` synthesized for records, so we always generate one for them.
+    if struct_def.has_attribute::<CsToString>() || is_plain {
+        builder.add_block(to_string(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsFormattable>() {
+        builder.add_block(formattable_to_string(struct_def));
+        builder.add_block(formattable_try_format(struct_def));
+        if let Some(field) = parsable_field(struct_def, options) {
+            builder.add_block(parsable(struct_def, field));
+        }
+    }
+
+    if struct_def.has_attribute::<CsNumeric>() {
+        builder.add_block(arithmetic_operators(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsValueEqualitySkipTagged>() {
+        builder.add_block(equality_skip_tagged(struct_def));
+    }
+
+    if is_plain {
+        builder.add_block(explicit_equality(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsFastEquals>() {
+        builder.add_block(fast_equals(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsStableHash>() {
+        builder.add_block(stable_hash_code(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsComparable>() {
+        builder.add_block(comparison(struct_def));
+    }
+
+    if struct_def.has_attribute::<CsBuilder>() {
+        builder.add_block(builder_class(struct_def));
+    }
+
+    let plumbing = plumbing_builder.map(|plumbing_builder| plumbing_builder.build());
+    (builder.build(), plumbing)
 }
 
-/// Generates an expression for encoding or decoding the fields of a struct.
-/// It checks which encodings this struct supports, and only generates code for the necessary encodings.
-fn generate_encoding_blocks(
-    fields: &[&Field],
-    encodings: SupportedEncodings,
-    encoding_fn: fn(&[&Field], Encoding) -> CodeBlock,
-    encoding_source: &'static str,
-) -> CodeBlock {
-    match encodings[..] {
-        [] => unreachable!("No supported encodings"),
-        [encoding] => encoding_fn(fields, encoding),
-        _ => {
-            let slice1_block = encoding_fn(fields, Encoding::Slice1);
-            let slice2_block = encoding_fn(fields, Encoding::Slice2);
+/// Returns the builder to add encoding and decoding members to. This is the builder of the plumbing part when the
+/// struct is split, and the struct's main builder otherwise.
+fn plumbing_target<'a>(
+    builder: &'a mut ContainerBuilder,
+    plumbing_builder: &'a mut Option<ContainerBuilder>,
+) -> &'a mut ContainerBuilder {
+    plumbing_builder.as_mut().unwrap_or(builder)
+}
 
-            // The encoding blocks are only empty for empty structs. But `Slice1` doesn't support empty structs.
-            // So this branch of the match statement is never hit, since it's for structs that support both encodings.
-            assert!(!slice1_block.is_empty() && !slice2_block.is_empty());
+/// Adds the `SkipLocalsInit` attribute to an encoding or decoding method when `--skip-locals-init` is set, unless the
+/// method's locals must be zero-initialized. Since the attribute is only accepted in unsafe contexts, the method's
+/// remarks mention it.
+fn add_skip_locals_init(function: &mut FunctionBuilder, options: &CsOptions) {
+    if options.skip_locals_init && function.can_skip_locals_init() {
+        function.add_skip_locals_init_attribute().add_comment(
+            "remarks",
+            "The locals of this method aren't zero-initialized, which requires <c>AllowUnsafeBlocks</c>.",
+        );
+    }
+}
 
-            // Only write one encoding block if `slice1_block` and `slice2_block` are the same.
-            if slice1_block.to_string() == slice2_block.to_string() {
-                return slice2_block;
-            }
+/// Returns the modifiers of a method that doesn't modify this struct. These methods are marked `readonly`, unless the
+/// struct is generated as a class, where `readonly` methods aren't allowed.
+fn readonly_modifiers(struct_def: &Struct, is_override: bool) -> String {
+    let mut modifiers = vec![struct_def.access_modifier()];
+    if is_override {
+        modifiers.push("override");
+    }
+    if !struct_def.has_attribute::<CsClass>() {
+        modifiers.push("readonly");
+    }
+    modifiers.join(" ")
+}
 
-            format!(
-                "\
-if ({encoding_source}.Encoding == SliceEncoding.Slice1)
+/// Returns the expressions for decoding this struct's fields, if the decode constructor can pass them directly to the
+/// main constructor. This requires the fields to be decoded the same way with each of the struct's encodings.
+fn chained_decode_arguments(struct_def: &Struct) -> Option<Vec<String>> {
+    let fields = struct_def.fields();
+    if fields.is_empty() {
+        return None;
+    }
+
+    let mut arguments = struct_def.supported_encodings()[..]
+        .iter()
+        .map(|encoding| decode_field_arguments(&fields, *encoding));
+    let first = arguments.next()??;
+    arguments.all(|other| other.as_ref() == Some(&first)).then_some(first)
+}
+
+/// Returns the type of the main constructor's parameter for this field. Read-only lists accept any sequence of
+/// elements, which is copied into the list's backing array.
+fn constructor_parameter_type(field: &Field, namespace: &str) -> String {
+    match field.data_type().concrete_type() {
+        Types::Sequence(sequence_def) if field.is_cs_readonly_list() => format!(
+            "global::System.Collections.Generic.IEnumerable<{}>",
+            sequence_def.element_type.field_type_string(namespace),
+        ),
+        _ => field.property_type_string(namespace),
+    }
+}
+
+/// Returns the type to use for parameters of this struct's type. Since classes can be null, this is nullable for
+/// structs that are generated as classes.
+fn parameter_type(struct_def: &Struct) -> String {
+    let escaped_identifier = struct_def.escape_identifier();
+    match struct_def.has_attribute::<CsClass>() {
+        true => escaped_identifier + "?",
+        false => escaped_identifier,
+    }
+}
+
+/// Generates an `Encode` overload that checks the number of bytes written by `encode_body` against a maximum.
+fn encode_with_options(struct_def: &Struct, encode_body: CodeBlock) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "int startByteCount = encoder.EncodedByteCount;");
+    body.writeln(&encode_body);
+    writeln!(
+        body,
+        "\
+if (maxEncodedByteCount >= 0 && encoder.EncodedByteCount - startByteCount > maxEncodedByteCount)
+{{
+    throw new global::System.InvalidOperationException(
+        $\"The encoded size of '{escaped_identifier}' exceeds the maximum of {{maxEncodedByteCount}} bytes.\");
+}}",
+    );
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "void",
+        "Encode",
+        FunctionType::BlockBody,
+    )
+    .add_comment(
+        "summary",
+        "Encodes the fields of this struct with a Slice encoder, while respecting the provided encode options.",
+    )
+    .add_parameter(
+        "ref SliceEncoder",
+        "encoder",
+        None,
+        Some("The Slice encoder.".to_owned()),
+    )
+    .add_parameter(
+        "int",
+        "maxEncodedByteCount",
+        None,
+        Some("The maximum number of bytes this struct can be encoded into, or <c>-1</c> for no maximum.".to_owned()),
+    )
+    .set_body(body)
+    .build()
+}
+
+/// Wraps the encoding of a framed struct's fields, so they're preceded by their encoded size on 4 bytes. Since this size
+/// isn't known until the fields are encoded, we reserve a placeholder for it and fill it in afterwards.
+fn encode_frame(encode_fields: CodeBlock) -> CodeBlock {
+    let mut code = CodeBlock::default();
+    writeln!(
+        code,
+        "global::System.Span<byte> framePlaceholder = encoder.GetPlaceholderSpan(4);"
+    );
+    writeln!(code, "int frameStartPos = encoder.EncodedByteCount;");
+    code.writeln(&encode_fields);
+    writeln!(
+        code,
+        "SliceEncoder.EncodeInt32(encoder.EncodedByteCount - frameStartPos, framePlaceholder);",
+    );
+    code
+}
+
+/// Wraps the decoding of a framed struct's fields, so they're decoded after their encoded size. The fields must fit in
+/// the remaining bytes of the decoder, and must consume exactly the number of bytes given by this size.
+fn decode_frame(struct_def: &Struct, decode_fields: CodeBlock) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    let mut code = CodeBlock::default();
+    writeln!(
+        code,
+        "\
+int frameSize = decoder.DecodeInt32();
+if (frameSize < 0 || frameSize > decoder.Remaining)
 {{
-{slice1_block}
+    throw new global::System.IO.InvalidDataException(
+        $\"Invalid frame size '{{frameSize}}' for '{escaped_identifier}': only {{decoder.Remaining}} bytes remain.\");
 }}
-else // Slice2
+long frameEndPos = decoder.Consumed + frameSize;",
+    );
+    code.writeln(&decode_fields);
+    writeln!(
+        code,
+        "\
+if (decoder.Consumed != frameEndPos)
+{{
+    throw new global::System.IO.InvalidDataException(
+        $\"The fields of '{escaped_identifier}' don't match its frame size of {{frameSize}} bytes.\");
+}}",
+    );
+    code
+}
+
+/// Generates the `ToString(string?, IFormatProvider?)` method of `IFormattable`, which formats the struct's only numeric
+/// field with the provided format and format provider.
+fn formattable_to_string(struct_def: &Struct) -> CodeBlock {
+    // The attribute was validated, so the struct is guaranteed to have exactly one numeric field.
+    let field = CsFormattable::formattable_field(struct_def).unwrap();
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "string",
+        "ToString",
+        FunctionType::ExpressionBody,
+    )
+    .add_comment(
+        "summary",
+        format!(
+            r#"Formats the value of <see cref="{}" /> using the specified format and format provider."#,
+            field.field_name(),
+        ),
+    )
+    .add_parameter(
+        "string?",
+        "format",
+        None,
+        Some("The format to use, or <see langword=\"null\" /> to use the default format.".to_owned()),
+    )
+    .add_parameter(
+        "global::System.IFormatProvider?",
+        "formatProvider",
+        None,
+        Some(
+            "The provider to use to format the value, or <see langword=\"null\" /> to use the current culture."
+                .to_owned(),
+        ),
+    )
+    .add_comment("returns", "The formatted value.")
+    .set_body(format!("this.{}.ToString(format, formatProvider)", field.field_name()).into())
+    .build()
+}
+
+/// Generates the `TryFormat` method of `ISpanFormattable`, which formats the struct's only numeric field into the
+/// provided span of characters, without allocating a string.
+fn formattable_try_format(struct_def: &Struct) -> CodeBlock {
+    // The attribute was validated, so the struct is guaranteed to have exactly one numeric field.
+    let field = CsFormattable::formattable_field(struct_def).unwrap();
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "bool",
+        "TryFormat",
+        FunctionType::ExpressionBody,
+    )
+    .add_comment(
+        "summary",
+        format!(
+            r#"Tries to format the value of <see cref="{}" /> into the provided span of characters."#,
+            field.field_name(),
+        ),
+    )
+    .add_parameter(
+        "global::System.Span<char>",
+        "destination",
+        None,
+        Some("The span in which to write the formatted value.".to_owned()),
+    )
+    .add_parameter(
+        "out int",
+        "charsWritten",
+        None,
+        Some("When this method returns, contains the number of characters written into the span.".to_owned()),
+    )
+    .add_parameter(
+        "global::System.ReadOnlySpan<char>",
+        "format",
+        None,
+        Some("The format to use, or an empty span to use the default format.".to_owned()),
+    )
+    .add_parameter(
+        "global::System.IFormatProvider?",
+        "provider",
+        None,
+        Some(
+            "The provider to use to format the value, or <see langword=\"null\" /> to use the current culture."
+                .to_owned(),
+        ),
+    )
+    .add_comment(
+        "returns",
+        "<see langword=\"true\" /> if the formatting was successful; otherwise, <see langword=\"false\" />.",
+    )
+    .set_body(
+        format!(
+            "this.{}.TryFormat(destination, out charsWritten, format, provider)",
+            field.field_name(),
+        )
+        .into(),
+    )
+    .build()
+}
+
+/// Returns the field parsed by the generated `IParsable<T>` methods, for formattable structs that wrap a single numeric
+/// field. Structs with other fields don't implement `IParsable<T>`, since there's nothing to parse these fields from.
+/// `IParsable<T>` was introduced in .NET 7, so no struct implements it when targeting an older framework.
+fn parsable_field<'a>(struct_def: &'a Struct, options: &CsOptions) -> Option<&'a Field> {
+    CsFormattable::formattable_field(struct_def)
+        .filter(|_| struct_def.fields().len() == 1 && options.target_framework.supports_modern_features())
+}
+
+/// Generates the `Parse` and `TryParse` methods of `IParsable<T>`, which parse the struct's only field with the
+/// `IParsable<T>` methods of its numeric type, and pass the parsed value to the main constructor.
+fn parsable(struct_def: &Struct, field: &Field) -> CodeBlock {
+    let access = struct_def.access_modifier();
+    let escaped_identifier = struct_def.escape_identifier();
+    let field_type = field.data_type().field_type_string(&struct_def.namespace());
+
+    let mut code = CodeBlock::default();
+    code.add_block(
+        FunctionBuilder::new(
+            &format!("{access} static"),
+            &escaped_identifier,
+            "Parse",
+            FunctionType::ExpressionBody,
+        )
+        .add_comment(
+            "summary",
+            format!(r#"Parses a string into a <see cref="{escaped_identifier}" />."#),
+        )
+        .add_parameter("string", "s", None, Some("The string to parse.".to_owned()))
+        .add_parameter(
+            "global::System.IFormatProvider?",
+            "provider",
+            None,
+            Some(
+                "The provider to use to parse the string, or <see langword=\"null\" /> to use the current culture."
+                    .to_owned(),
+            ),
+        )
+        .add_comment("returns", "The parsed value.")
+        .set_body(format!("new({field_type}.Parse(s, provider))").into())
+        .build(),
+    );
+    code.add_block(
+        FunctionBuilder::new(&format!("{access} static"), "bool", "TryParse", FunctionType::BlockBody)
+            .add_comment(
+                "summary",
+                format!(r#"Tries to parse a string into a <see cref="{escaped_identifier}" />."#),
+            )
+            .add_parameter_with_attributes(
+                "string?",
+                "s",
+                None,
+                Some("The string to parse, or <see langword=\"null\" />.".to_owned()),
+                &["global::System.Diagnostics.CodeAnalysis.NotNullWhen(true)".to_owned()],
+            )
+            .add_parameter(
+                "global::System.IFormatProvider?",
+                "provider",
+                None,
+                Some("The provider to use to parse the string, or <see langword=\"null\" /> to use the current culture.".to_owned()),
+            )
+            .add_parameter_with_attributes(
+                &format!("out {escaped_identifier}"),
+                "result",
+                None,
+                Some("When this method returns <see langword=\"true\" />, the parsed value.".to_owned()),
+                &["global::System.Diagnostics.CodeAnalysis.MaybeNullWhen(false)".to_owned()],
+            )
+            .add_comment(
+                "returns",
+                "<see langword=\"true\" /> if <paramref name=\"s\" /> was parsed successfully; otherwise, <see langword=\"false\" />.",
+            )
+            .set_body(
+                format!(
+                    "\
+if ({field_type}.TryParse(s, provider, out {field_type} value))
 {{
-{slice2_block}
+    result = new(value);
+    return true;
 }}
-",
-                slice1_block = slice1_block.indent(),
-                slice2_block = slice2_block.indent(),
+result = default;
+return false;"
+                )
+                .into(),
+            )
+            .build(),
+    );
+    code
+}
+
+/// Generates the `+`, `-`, `*` and `/` operators of a struct wrapping a single numeric field, which apply the operator
+/// to the fields of both operands, and wrap the result into a new struct. C# promotes the operands of arithmetic
+/// operators to `int` when their type is smaller, so the result is cast back to the field's type for these types.
+fn arithmetic_operators(struct_def: &Struct) -> CodeBlock {
+    // The attribute was validated, so the struct is guaranteed to have a single numeric field.
+    let field = CsNumeric::numeric_field(struct_def).unwrap();
+    let escaped_identifier = struct_def.escape_identifier();
+    let field_name = field.field_name();
+    let field_type = field.data_type().field_type_string(&struct_def.namespace());
+    let is_promoted = matches!(
+        field.data_type().concrete_type(),
+        Types::Primitive(Primitive::Int8 | Primitive::UInt8 | Primitive::Int16 | Primitive::UInt16),
+    );
+
+    let mut code = CodeBlock::default();
+    for (operator, verb) in [("+", "Adds"), ("-", "Subtracts"), ("*", "Multiplies"), ("/", "Divides")] {
+        let operation = format!("left.{field_name} {operator} right.{field_name}");
+        let result = match is_promoted {
+            true => format!("({field_type})({operation})"),
+            false => operation,
+        };
+        code.add_block(
+            FunctionBuilder::new(
+                &format!("{} static", struct_def.access_modifier()),
+                &escaped_identifier,
+                &format!("operator {operator}"),
+                FunctionType::ExpressionBody,
+            )
+            .add_comment(
+                "summary",
+                format!(r#"{verb} two <see cref="{escaped_identifier}" /> values."#),
+            )
+            .add_parameter(&escaped_identifier, "left", None, Some("The left operand.".to_owned()))
+            .add_parameter(
+                &escaped_identifier,
+                "right",
+                None,
+                Some("The right operand.".to_owned()),
             )
-            .into()
+            .add_comment("returns", "The result of the operation.")
+            .set_body(format!("new({result})").into())
+            .build(),
+        );
+    }
+    code
+}
+
+/// Generates a `Deconstruct` method whose out parameters match the parameters of the struct's main constructor.
+fn deconstruct(struct_def: &Struct) -> CodeBlock {
+    let fields = struct_def.fields();
+    let namespace = struct_def.namespace();
+
+    let mut builder = FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "void",
+        "Deconstruct",
+        FunctionType::BlockBody,
+    );
+    builder.add_comment("summary", "Deconstructs this struct into its fields.");
+
+    for field in &fields {
+        builder.add_parameter(
+            &format!("out {}", field.property_type_string(&namespace)),
+            &field.parameter_name(),
+            None,
+            field.formatted_doc_comment_summary(),
+        );
+    }
+    builder.set_body({
+        let mut code = CodeBlock::default();
+        for field in &fields {
+            writeln!(code, "{} = this.{};", field.parameter_name(), field.field_name());
         }
+        code
+    });
+
+    builder.build()
+}
+
+/// Generates a `DeepClone` method, which calls the main constructor with a copy of each field. Unlike `with`
+/// expressions, the clone doesn't share its mutable collections with this struct. The method can't be named `Clone`,
+/// since C# doesn't allow records to declare members with this name.
+fn deep_clone(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    let mut constructor_call = FunctionCallBuilder::new("new");
+    constructor_call.use_semicolon(false).arguments_on_newline(true);
+    for field in struct_def.fields() {
+        constructor_call.add_argument(field_copy_expression(field));
+    }
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        &escaped_identifier,
+        "DeepClone",
+        FunctionType::ExpressionBody,
+    )
+    .add_comment(
+        "summary",
+        format!(r#"Creates a new instance of <see cref="{escaped_identifier}" /> with copies of the fields of this instance."#),
+    )
+    .add_comment(
+        "remarks",
+        "Mutable collections are copied into new collections, but their elements are not cloned.",
+    )
+    .add_comment("returns", "The new instance.")
+    .set_body(constructor_call.build())
+    .build()
+}
+
+/// Generates the declarations of the `OnDecoded` and `OnEncoding` partial methods. The decode constructor calls
+/// `OnDecoded` once all fields are decoded, and `Encode` calls `OnEncoding` before encoding any field. Since `Encode` is
+/// readonly, `OnEncoding` must be readonly too, unless the struct is generated as a class.
+fn lifecycle_hooks(struct_def: &Struct) -> CodeBlock {
+    let on_encoding_modifiers = match struct_def.has_attribute::<CsClass>() {
+        true => "partial",
+        false => "readonly partial",
+    };
+
+    let mut code = CodeBlock::default();
+    code.add_block(
+        FunctionBuilder::new("partial", "void", "OnDecoded", FunctionType::Declaration)
+            .add_comment("summary", "Called after the fields of this struct are decoded.")
+            .add_comment(
+                "remarks",
+                "This method is called at the end of the decode constructor, once all fields are assigned.",
+            )
+            .build(),
+    );
+    code.add_block(
+        FunctionBuilder::new(on_encoding_modifiers, "void", "OnEncoding", FunctionType::Declaration)
+            .add_comment("summary", "Called before the fields of this struct are encoded.")
+            .add_parameter(
+                "ref SliceEncoder",
+                "encoder",
+                None,
+                Some("The Slice encoder.".to_owned()),
+            )
+            .build(),
+    );
+    code
+}
+
+/// Generates the copy constructor of a struct that is generated as a record class. When declared explicitly, this
+/// constructor replaces the one synthesized by the C# compiler, so it must copy each field itself. Since the record
+/// class is sealed, its copy constructor must be private. `with` expressions set their properties after calling it, so
+/// it only validates the copied values.
+fn copy_constructor(struct_def: &Struct, options: &CsOptions) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let fields = struct_def.fields();
+
+    let mut builder = FunctionBuilder::new("private", "", &escaped_identifier, FunctionType::BlockBody);
+    builder
+        .add_comment(
+            "summary",
+            format!(r#"Constructs a new instance of <see cref="{escaped_identifier}" /> with the fields of another instance."#),
+        )
+        .add_parameter(
+            &escaped_identifier,
+            "original",
+            None,
+            Some("The instance to copy.".to_owned()),
+        )
+        .add_comment(
+            "remarks",
+            "This constructor is called by <see langword=\"with\" /> expressions before they set any property, so it only validates the copied fields.",
+        );
+    if fields.iter().any(|field| is_required_member(field, options)) {
+        builder.add_sets_required_members_attribute();
+    }
+    builder.set_body({
+        let mut code = CodeBlock::default();
+        for field in &fields {
+            let field_name = match field.is_cs_readonly_list() {
+                true => field.backing_field_name(),
+                false => field.field_name(),
+            };
+            writeln!(code, "this.{field_name} = original.{field_name};");
+        }
+        writeln!(code, "Validate();");
+        code
+    });
+
+    builder.build()
+}
+
+/// Generates a static `FieldTags` dictionary, which maps the C# property name of each tagged field to its Slice tag.
+/// Structs without tagged fields, including all compact structs, get an empty dictionary.
+fn field_tags(struct_def: &Struct, options: &CsOptions) -> CodeBlock {
+    let tagged_fields = struct_def
+        .fields()
+        .into_iter()
+        .filter_map(|field| field.tag().map(|tag| (field.cs_identifier(Case::Pascal), tag)))
+        .collect::<Vec<_>>();
+
+    let entries = tagged_fields
+        .iter()
+        .map(|(name, tag)| format!(r#"["{name}"] = {tag},"#))
+        .collect::<Vec<_>>();
+
+    // Frozen dictionaries were introduced in .NET 8. Older frameworks use a regular dictionary, which is only exposed
+    // through a read-only interface.
+    let value = match (entries.as_slice(), options.target_framework.supports_modern_features()) {
+        ([], true) => "global::System.Collections.Frozen.FrozenDictionary<string, int>.Empty".to_owned(),
+        ([], false) => "new global::System.Collections.Generic.Dictionary<string, int>()".to_owned(),
+        (_, true) => format!(
+            "\
+global::System.Collections.Frozen.FrozenDictionary.ToFrozenDictionary(
+    new global::System.Collections.Generic.Dictionary<string, int>
+    {{
+        {entries}
+    }})",
+            entries = entries.join("\n        "),
+        ),
+        (_, false) => format!(
+            "\
+new global::System.Collections.Generic.Dictionary<string, int>
+{{
+    {entries}
+}}",
+            entries = entries.join("\n    "),
+        ),
+    };
+
+    format!(
+        r#"/// <summary>Gets the Slice tags of the tagged fields of this struct, keyed by the names of their C# properties.</summary>
+{access} static readonly global::System.Collections.Generic.IReadOnlyDictionary<string, int> FieldTags =
+    {value};"#,
+        access = struct_def.access_modifier(),
+        value = CodeBlock::from(value).indent(),
+    )
+    .into()
+}
+
+/// Generates a static `Default` field, initialized with the main constructor and the default values provided by
+/// `cs::customDefault`. Fields without a provided default value are initialized to `default`.
+fn custom_default_field(struct_def: &Struct, custom_default: &CsCustomDefault) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    let mut constructor_call = FunctionCallBuilder::new("new");
+    constructor_call.use_semicolon(false);
+    for field in struct_def.fields() {
+        constructor_call.add_argument(custom_default.default_value_of(field.identifier()).unwrap_or("default"));
+    }
+
+    let mut code = CodeBlock::default();
+    writeln!(
+        code,
+        r#"/// <summary>The default value of <see cref="{escaped_identifier}" />.</summary>"#,
+    );
+    if !struct_def.has_attribute::<CsClass>() {
+        writeln!(
+            code,
+            "\
+/// <remarks>This field is initialized with the default values provided in the Slice definition. Unlike this field,
+/// <c>default({escaped_identifier})</c> still zero-initializes all fields.</remarks>",
+        );
+    }
+    writeln!(
+        code,
+        "{} static readonly {escaped_identifier} Default = {};",
+        struct_def.access_modifier(),
+        constructor_call.build(),
+    );
+    code
+}
+
+/// Generates a nested `Builder` class, with a fluent `WithX` setter for each optional field. Since non-optional fields
+/// have no sensible default value, they are passed to `Build`, which calls the main constructor.
+fn builder_class(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let fields = struct_def.fields();
+    let namespace = struct_def.namespace();
+    let (optional_fields, required_fields): (Vec<_>, Vec<_>) =
+        fields.iter().partition(|field| field.data_type().is_optional);
+
+    let mut builder = ContainerBuilder::new(&format!("{} sealed class", struct_def.access_modifier()), "Builder");
+    builder.add_comment(
+        "summary",
+        format!(r#"Builds instances of <see cref="{escaped_identifier}" /> one field at a time."#),
+    );
+
+    if !optional_fields.is_empty() {
+        let mut declarations = CodeBlock::default();
+        for field in &optional_fields {
+            writeln!(
+                declarations,
+                "private {} {};",
+                constructor_parameter_type(field, &namespace),
+                field.backing_field_name(),
+            );
+        }
+        builder.add_block(declarations);
+    }
+
+    for field in &optional_fields {
+        let mut setter = FunctionBuilder::new(
+            struct_def.access_modifier(),
+            "Builder",
+            &format!("With{}", field.cs_identifier(Case::Pascal)),
+            FunctionType::BlockBody,
+        );
+        setter
+            .add_comment(
+                "summary",
+                format!(
+                    r#"Sets the value of <see cref="{escaped_identifier}.{}" />."#,
+                    field.field_name(),
+                ),
+            )
+            .add_parameter(
+                &constructor_parameter_type(field, &namespace),
+                "value",
+                None,
+                Some("The value of the field.".to_owned()),
+            )
+            .add_comment("returns", "This builder.")
+            .set_body(format!("{} = value;\nreturn this;", field.backing_field_name()).into());
+        builder.add_block(setter.build());
+    }
+
+    let mut constructor_call = FunctionCallBuilder::new(format!("new {escaped_identifier}"));
+    constructor_call.use_semicolon(false);
+    for field in &fields {
+        match field.data_type().is_optional {
+            true => constructor_call.add_argument(field.backing_field_name()),
+            false => constructor_call.add_argument(field.parameter_name()),
+        };
+    }
+
+    let mut build = FunctionBuilder::new(
+        struct_def.access_modifier(),
+        &escaped_identifier,
+        "Build",
+        FunctionType::ExpressionBody,
+    );
+    build.add_comment(
+        "summary",
+        format!(
+            r#"Constructs a new instance of <see cref="{escaped_identifier}" /> from the values set on this builder."#
+        ),
+    );
+    for field in &required_fields {
+        build.add_parameter(
+            &constructor_parameter_type(field, &namespace),
+            &field.parameter_name(),
+            None,
+            field.formatted_doc_comment_summary(),
+        );
+    }
+    build
+        .add_comment("returns", "The new instance.")
+        .set_body(constructor_call.build());
+    builder.add_block(build.build());
+
+    builder.build()
+}
+
+/// Returns the C# encodings supported by this struct.
+pub fn cs_encodings(struct_def: &Struct) -> Vec<&'static str> {
+    struct_def.supported_encodings()[..]
+        .iter()
+        .map(|encoding| encoding.to_cs_encoding())
+        .collect()
+}
+
+/// Returns a statement that throws a `NotSupportedException` if the `encoding` parameter isn't one of `encodings`.
+pub fn check_encoding_parameter(struct_def: &Struct, encodings: &[&str]) -> String {
+    format!(
+        "\
+if ({condition})
+{{
+    throw new global::System.NotSupportedException(
+        $\"The struct '{identifier}' cannot be encoded with the {{encoding}} encoding.\");
+}}",
+        condition = encodings
+            .iter()
+            .map(|encoding| format!("encoding != {encoding}"))
+            .collect::<Vec<_>>()
+            .join(" && "),
+        identifier = struct_def.escape_identifier(),
+    )
+}
+
+/// Generates an `EncodeToArray` method that encodes this struct into a new byte array, with the provided encoding.
+/// If the struct only supports a single encoding, the encoding parameter defaults to that encoding.
+fn encode_to_array(struct_def: &Struct) -> CodeBlock {
+    let encodings = cs_encodings(struct_def);
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "{}", check_encoding_parameter(struct_def, &encodings));
+    writeln!(
+        body,
+        "\
+var bufferWriter = new global::System.Buffers.ArrayBufferWriter<byte>();
+var encoder = new SliceEncoder(bufferWriter, encoding);
+Encode(ref encoder);
+return bufferWriter.WrittenSpan.ToArray();",
+    );
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "byte[]",
+        "EncodeToArray",
+        FunctionType::BlockBody,
+    )
+    .add_comment("summary", "Encodes this struct into a new byte array.")
+    .add_parameter(
+        "SliceEncoding",
+        "encoding",
+        match encodings.as_slice() {
+            [encoding] => Some(*encoding),
+            _ => None,
+        },
+        Some("The encoding to encode this struct with.".to_owned()),
+    )
+    .add_comment("returns", "A byte array holding the encoded struct.")
+    .add_comment_with_attribute(
+        "exception",
+        "cref",
+        "global::System.NotSupportedException",
+        "Thrown if this struct doesn't support the provided encoding.",
+    )
+    .set_body(body)
+    .build()
+}
+
+/// Generates a `TryEncode` method that encodes this struct into a caller-supplied buffer, with the provided encoding.
+/// A `SliceEncoder` can't write into a span, so the struct is encoded into a buffer rented from the shared array pool,
+/// through a `TryEncodeBufferWriter`. The encoded bytes, including the tag end marker of non-compact structs, are
+/// copied into the caller's buffer only if they fit.
+fn try_encode(struct_def: &Struct) -> CodeBlock {
+    let encodings = cs_encodings(struct_def);
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "{}", check_encoding_parameter(struct_def, &encodings));
+    writeln!(
+        body,
+        "\
+// The rented buffer is a little larger than the destination, since the encoder requests 8 bytes to encode a varint,
+// even when it writes fewer bytes.
+byte[] buffer = global::System.Buffers.ArrayPool<byte>.Shared.Rent(destination.Length + 8);
+try
+{{
+    var bufferWriter = new TryEncodeBufferWriter(buffer);
+    var encoder = new SliceEncoder(bufferWriter, encoding);
+    Encode(ref encoder);
+    if (bufferWriter.HasOverflowed || bufferWriter.WrittenSpan.Length > destination.Length)
+    {{
+        bytesWritten = 0;
+        return false;
+    }}
+    bufferWriter.WrittenSpan.CopyTo(destination);
+    bytesWritten = bufferWriter.WrittenSpan.Length;
+    return true;
+}}
+finally
+{{
+    global::System.Buffers.ArrayPool<byte>.Shared.Return(buffer);
+}}",
+    );
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "bool",
+        "TryEncode",
+        FunctionType::BlockBody,
+    )
+    .add_comment("summary", "Encodes this struct into the provided buffer.")
+    .add_parameter(
+        "global::System.Span<byte>",
+        "destination",
+        None,
+        Some("The buffer to encode this struct into.".to_owned()),
+    )
+    .add_parameter(
+        "out int",
+        "bytesWritten",
+        None,
+        Some("When this method returns <see langword=\"true\" />, the number of bytes written to <paramref name=\"destination\" />.".to_owned()),
+    )
+    .add_parameter(
+        "SliceEncoding",
+        "encoding",
+        match encodings.as_slice() {
+            [encoding] => Some(*encoding),
+            _ => None,
+        },
+        Some("The encoding to encode this struct with.".to_owned()),
+    )
+    .add_comment(
+        "returns",
+        "<see langword=\"true\" /> if this struct was encoded into <paramref name=\"destination\" />; <see langword=\"false\" /> if <paramref name=\"destination\" /> is too small.",
+    )
+    .add_comment_with_attribute(
+        "exception",
+        "cref",
+        "global::System.NotSupportedException",
+        "Thrown if this struct doesn't support the provided encoding.",
+    )
+    .set_body(body)
+    .build()
+}
+
+/// Generates the private buffer writer used by `TryEncode`. It writes into a fixed-size buffer and never grows: when the
+/// encoder requests more memory than the buffer has left, it records that it overflowed and provides scratch memory
+/// instead, so that the encoding completes without throwing. `TryEncode` then checks whether the struct fit.
+fn try_encode_buffer_writer() -> CodeBlock {
+    let mut builder = ContainerBuilder::new("private sealed class", "TryEncodeBufferWriter");
+    builder
+        .add_comment(
+            "summary",
+            "Implements a buffer writer over a fixed-size buffer, which records that it overflowed instead of growing.",
+        )
+        .add_base("global::System.Buffers.IBufferWriter<byte>".to_owned());
+
+    builder.add_block(
+        "\
+internal bool HasOverflowed { get; private set; }
+
+internal global::System.ReadOnlySpan<byte> WrittenSpan => new(_buffer, 0, _written);
+
+private readonly byte[] _buffer;
+
+private int _written;"
+            .into(),
+    );
+    builder.add_block("internal TryEncodeBufferWriter(byte[] buffer) => _buffer = buffer;".into());
+    builder.add_block(
+        "\
+public void Advance(int count)
+{
+    // Bytes written to scratch memory aren't part of the buffer.
+    if (!HasOverflowed)
+    {
+        _written += count;
+    }
+}"
+        .into(),
+    );
+    builder.add_block(
+        "\
+public global::System.Memory<byte> GetMemory(int sizeHint = 0)
+{
+    if (HasOverflowed || global::System.Math.Max(sizeHint, 1) > _buffer.Length - _written)
+    {
+        HasOverflowed = true;
+        return new byte[global::System.Math.Max(sizeHint, 256)];
+    }
+    return new(_buffer, _written, _buffer.Length - _written);
+}"
+        .into(),
+    );
+    builder.add_block("public global::System.Span<byte> GetSpan(int sizeHint = 0) => GetMemory(sizeHint).Span;".into());
+
+    builder.build()
+}
+
+/// Returns the format string of the `DebuggerDisplay` attribute for a struct with the provided fields, which displays
+/// its first two scalar fields (primitives and enums), or `None` if the struct has no scalar fields.
+fn debugger_display(fields: &[&Field]) -> Option<String> {
+    let displayed_fields = fields
+        .iter()
+        .filter(|field| match field.data_type().concrete_type() {
+            Types::Primitive(primitive) => !matches!(primitive, Primitive::AnyClass),
+            Types::Enum(_) => true,
+            _ => false,
+        })
+        .take(2)
+        .map(|field| format!("{name} = {{{name}}}", name = field.field_name()))
+        .collect::<Vec<_>>();
+
+    (!displayed_fields.is_empty()).then(|| displayed_fields.join(", "))
+}
+
+/// Generates static `EncodeAction` and `DecodeFunc` fields that encode and decode this struct, so it can be passed to
+/// the methods that encode and decode collections without any extra code.
+fn delegates(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let access = struct_def.access_modifier();
+
+    format!(
+        r#"/// <summary>Encodes a <see cref="{escaped_identifier}" /> with a Slice encoder.</summary>
+{access} static readonly EncodeAction<{escaped_identifier}> SliceEncode =
+    (ref SliceEncoder encoder, {escaped_identifier} value) => value.Encode(ref encoder);
+
+/// <summary>Decodes a <see cref="{escaped_identifier}" /> from a Slice decoder, using its decode constructor.</summary>
+{access} static readonly DecodeFunc<{escaped_identifier}> SliceDecode =
+    (ref SliceDecoder decoder) => new {escaped_identifier}(ref decoder);"#
+    )
+    .into()
+}
+
+/// Generates a `GetEncodedSize` method that computes the number of bytes needed to encode this struct. The sizes of
+/// fixed-size fields, the bit sequence, and the tag end marker are computed by the compiler, only the sizes of
+/// variable-size fields are computed at runtime.
+fn encoded_size(struct_def: &Struct) -> CodeBlock {
+    let fields = struct_def.fields();
+
+    let fixed_fields_size = fields
+        .iter()
+        .filter(|field| !field.is_tagged() && !field.data_type().is_optional)
+        .filter_map(|field| fixed_size_of(&field.data_type()))
+        .sum::<u32>();
+    let fixed_sizes = struct_def.supported_encodings()[..]
+        .iter()
+        .map(|encoding| {
+            let bit_sequence_size = get_bit_sequence_size(*encoding, &fields).div_ceil(8) as u32;
+            let tag_end_marker_size = if struct_def.is_compact { 0 } else { 1 };
+            let frame_size = if struct_def.has_attribute::<CsFramed>() { 4 } else { 0 };
+            fixed_fields_size + bit_sequence_size + tag_end_marker_size + frame_size
+        })
+        .collect::<Vec<_>>();
+    let fixed_size = match fixed_sizes.as_slice() {
+        [slice1_size, slice2_size] if slice1_size != slice2_size => {
+            format!("encoding == SliceEncoding.Slice1 ? {slice1_size} : {slice2_size}")
+        }
+        sizes => sizes[0].to_string(),
+    };
+
+    let mut field_sizes = CodeBlock::default();
+    for field in &fields {
+        let data_type = field.data_type();
+        if !field.is_tagged() && !data_type.is_optional {
+            if fixed_size_of(&data_type).is_none() {
+                write_encoded_size(&mut field_sizes, &data_type, &format!("this.{}", field.field_name()));
+            }
+            continue;
+        }
+
+        let field_name = field.field_name();
+        let value = match data_type.is_value_type() {
+            true => format!("this.{field_name}.Value"),
+            false => format!("this.{field_name}"),
+        };
+        let mut value_size = CodeBlock::default();
+        write_encoded_size(&mut value_size, &data_type, &value);
+
+        // Tagged fields are encoded with their tag and the size of their value, followed by their value.
+        if let Some(tag) = field.tag() {
+            value_size = format!(
+                "\
+start = size;
+{value_size}
+size += SliceEncoder.GetVarInt62EncodedSize({tag}) + GetSizeLength(encoding, size - start);"
+            )
+            .into();
+        }
+
+        writeln!(
+            field_sizes,
+            "\
+if (this.{field_name} is not null)
+{{
+    {value_size}
+}}",
+            value_size = value_size.indent(),
+        );
+    }
+
+    let uses_count = fields.iter().any(|field| {
+        matches!(
+            field.data_type().concrete_type(),
+            Types::Primitive(Primitive::String) | Types::Sequence(_),
+        )
+    });
+    let uses_start = fields.iter().any(|field| field.is_tagged());
+
+    let mut body = CodeBlock::default();
+    writeln!(body, "int size = {fixed_size};");
+    if uses_count {
+        writeln!(body, "int count;");
+    }
+    if uses_start {
+        writeln!(body, "int start;");
+    }
+    // The sizes of variable-size fields are summed in a checked context, so that an overflow throws instead of
+    // returning a negative size.
+    if !field_sizes.is_empty() {
+        writeln!(body, "checked\n{{\n    {}\n}}", field_sizes.indent());
+    }
+    writeln!(body, "return size;");
+    if uses_count || uses_start {
+        writeln!(
+            body,
+            "
+static int GetSizeLength(SliceEncoding encoding, int value) =>
+    encoding == SliceEncoding.Slice1 ? (value < 255 ? 1 : 5) : SliceEncoder.GetVarUInt62EncodedSize((ulong)value);",
+        );
+    }
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "int",
+        "GetEncodedSize",
+        FunctionType::BlockBody,
+    )
+    .add_comment(
+        "summary",
+        "Computes the number of bytes needed to encode this struct with the provided encoding.",
+    )
+    .add_parameter(
+        "SliceEncoding",
+        "encoding",
+        None,
+        Some("The encoding to compute the encoded size with.".to_owned()),
+    )
+    .add_comment("returns", "The number of bytes needed to encode this struct.")
+    .set_body(body)
+    .build()
+}
+
+/// Returns the encoded size of the provided type, if the type always has the same encoded size.
+fn fixed_size_of(type_ref: &TypeRef) -> Option<u32> {
+    match type_ref.concrete_type() {
+        Types::Primitive(primitive) => primitive.fixed_wire_size(),
+        Types::Struct(struct_def) => struct_def.fixed_wire_size(),
+        Types::Enum(enum_def) => enum_def.fixed_wire_size(),
+        _ => None,
+    }
+}
+
+/// Writes the statements that add the encoded size of `value` to the `size` variable.
+/// The encoded size of the value's type must be computable, see `TypeRefExt::has_computable_encoded_size`.
+fn write_encoded_size(code: &mut CodeBlock, type_ref: &TypeRef, value: &str) {
+    if let Some(size) = fixed_size_of(type_ref) {
+        writeln!(code, "size += {size};");
+        return;
+    }
+
+    match type_ref.concrete_type() {
+        Types::Primitive(Primitive::String) => writeln!(
+            code,
+            "\
+count = global::System.Text.Encoding.UTF8.GetByteCount({value});
+size += GetSizeLength(encoding, count) + count;",
+        ),
+        Types::Primitive(Primitive::VarInt32 | Primitive::VarInt62) => {
+            writeln!(code, "size += SliceEncoder.GetVarInt62EncodedSize({value});")
+        }
+        Types::Primitive(Primitive::VarUInt32 | Primitive::VarUInt62) => {
+            writeln!(code, "size += SliceEncoder.GetVarUInt62EncodedSize({value});")
+        }
+        Types::Struct(_) => writeln!(code, "size += {value}.GetEncodedSize(encoding);"),
+        Types::Sequence(sequence_def) => {
+            let element_size = sequence_def.element_type.fixed_wire_size().unwrap();
+            let count = match type_ref.is_read_only_memory() {
+                true => format!("{value}.Length"),
+                false => format!("global::System.Linq.Enumerable.Count({value})"),
+            };
+            writeln!(
+                code,
+                "\
+count = {count};
+size += GetSizeLength(encoding, count) + {element_size} * count;",
+            )
+        }
+        _ => unreachable!("'{value}' doesn't have a computable encoded size"),
+    }
+}
+
+/// Generates a static `TryDecode` method that wraps the decode constructor, and returns `false` instead of throwing
+/// when the decoded data is invalid.
+fn try_decode(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    // Classes can be null, so we let the caller know that the value is only null when decoding fails.
+    let value_type = match struct_def.has_attribute::<CsClass>() {
+        true => format!("[global::System.Diagnostics.CodeAnalysis.NotNullWhen(true)] out {escaped_identifier}?"),
+        false => format!("out {escaped_identifier}"),
+    };
+
+    FunctionBuilder::new(
+        &(struct_def.access_modifier().to_owned() + " static"),
+        "bool",
+        "TryDecode",
+        FunctionType::BlockBody,
+    )
+    .add_comment(
+        "summary",
+        format!(r#"Tries to decode a <see cref="{escaped_identifier}" /> from a Slice decoder."#),
+    )
+    .add_comment(
+        "remarks",
+        "If the decoding fails, the decoder is reset to the position it had before this method was called.",
+    )
+    .add_parameter(
+        "ref SliceDecoder",
+        "decoder",
+        None,
+        Some("The Slice decoder.".to_owned()),
+    )
+    .add_parameter(
+        &value_type,
+        "value",
+        None,
+        Some(
+            r#"When this method returns <see langword="true" />, contains the decoded struct; otherwise, contains <see langword="default" />."#
+                .to_owned(),
+        ),
+    )
+    .add_comment(
+        "returns",
+        r#"<see langword="true" /> if the struct was successfully decoded; otherwise, <see langword="false" />."#,
+    )
+    .set_body(
+        format!(
+            "\
+SliceDecoder snapshot = decoder;
+try
+{{
+    value = new {escaped_identifier}(ref decoder);
+    return true;
+}}
+catch (global::System.IO.InvalidDataException)
+{{
+    decoder = snapshot;
+    value = default;
+    return false;
+}}"
+        )
+        .into(),
+    )
+    .build()
+}
+
+/// Generates a `ToString` override that prints the struct's name, and the name and value of each of its fields.
+fn to_string(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+
+    let mut body = CodeBlock::default();
+    writeln!(
+        body,
+        "var builder = new global::System.Text.StringBuilder(\"{escaped_identifier} {{\");"
+    );
+    for (i, field) in struct_def.fields().iter().enumerate() {
+        let field_name = field.field_name();
+        let separator = if i == 0 { " " } else { ", " };
+        let data_type = field.data_type();
+
+        // Fields mapped to a custom C# type with `cs::type` could be either value types or reference types.
+        let value = if field.has_attribute::<CsType>() {
+            format!(r#"(object?)this.{field_name} ?? "null""#)
+        } else if matches!(data_type.concrete_type(), Types::Primitive(Primitive::String)) {
+            format!(r#"this.{field_name} is null ? "null" : $"\"{{this.{field_name}}}\"""#)
+        } else if data_type.is_optional || !data_type.is_value_type() {
+            format!(r#"this.{field_name}?.ToString() ?? "null""#)
+        } else {
+            format!("this.{field_name}")
+        };
+        writeln!(body, "builder.Append(\"{separator}{field_name} = \").Append({value});");
+    }
+    writeln!(body, "builder.Append(\" }}\");");
+    writeln!(body, "return builder.ToString();");
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, true),
+        "string",
+        "ToString",
+        FunctionType::BlockBody,
+    )
+    .add_comment(
+        "summary",
+        "Returns a string that represents this struct, including the names and values of its fields.",
+    )
+    .add_comment("returns", "A string that represents this struct.")
+    .set_body(body)
+    .build()
+}
+
+/// Generates `Equals` and `GetHashCode` methods that only consider the struct's non-tagged fields.
+/// The equality operators synthesized for record structs call this `Equals` method.
+fn equality_skip_tagged(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let namespace = struct_def.namespace();
+    let fields = struct_def
+        .fields()
+        .into_iter()
+        .filter(|field| !field.is_tagged())
+        .collect::<Vec<_>>();
+
+    // Since classes can be null, we check that the other class isn't null before comparing its fields.
+    let is_class = struct_def.has_attribute::<CsClass>();
+    let null_check = is_class.then(|| "other is not null".to_owned());
+
+    let comparisons = null_check
+        .into_iter()
+        .chain(field_comparisons(&fields, &namespace))
+        .collect::<Vec<_>>();
+    let equals_body = match comparisons.is_empty() {
+        true => "true".to_owned(),
+        false => comparisons.join(" &&\n"),
+    };
+
+    let mut equals = FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "bool",
+        "Equals",
+        FunctionType::ExpressionBody,
+    );
+    equals
+        .add_comment(
+            "summary",
+            format!(r#"Indicates whether this struct is equal to another <see cref="{escaped_identifier}" />. Tagged fields are not compared."#),
+        )
+        .add_parameter(
+            &parameter_type(struct_def),
+            "other",
+            None,
+            Some("The struct to compare with this struct.".to_owned()),
+        )
+        .add_comment(
+            "returns",
+            "<see langword=\"true\" /> if the non-tagged fields of both structs are equal; otherwise, <see langword=\"false\" />.",
+        )
+        .set_body(equals_body.into());
+
+    let mut hash_code_body = CodeBlock::default();
+    writeln!(hash_code_body, "var hash = new global::System.HashCode();");
+    for field in &fields {
+        writeln!(hash_code_body, "hash.Add(this.{});", field.field_name());
+    }
+    writeln!(hash_code_body, "return hash.ToHashCode();");
+
+    let mut get_hash_code = FunctionBuilder::new(
+        &readonly_modifiers(struct_def, true),
+        "int",
+        "GetHashCode",
+        FunctionType::BlockBody,
+    );
+    get_hash_code
+        .add_comment(
+            "summary",
+            "Computes the hash code of this struct. Tagged fields are not included.",
+        )
+        .add_comment("returns", "The hash code of this struct.")
+        .set_body(hash_code_body);
+
+    let mut code = CodeBlock::default();
+    code.add_block(equals.build());
+    code.add_block(get_hash_code.build());
+    code
+}
+
+/// Generates the equality members that the C# compiler synthesizes for records, for structs that are generated as plain
+/// structs and classes: `Equals(T)`, `Equals(object?)`, `GetHashCode`, and the equality operators. `Equals(T)` and
+/// `GetHashCode` are only generated if they're not already generated by `cs::valueEqualitySkipTagged` or
+/// `cs::stableHash`.
+fn explicit_equality(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let namespace = struct_def.namespace();
+    let fields = struct_def.fields();
+    let is_class = struct_def.has_attribute::<CsClass>();
+    let skip_tagged = struct_def.has_attribute::<CsValueEqualitySkipTagged>();
+
+    let mut code = CodeBlock::default();
+
+    if !skip_tagged {
+        // Since classes can be null, we check that the other class isn't null before comparing its fields.
+        let comparisons = is_class
+            .then(|| "other is not null".to_owned())
+            .into_iter()
+            .chain(field_comparisons(&fields, &namespace))
+            .collect::<Vec<_>>();
+        let equals_body = match comparisons.is_empty() {
+            true => "true".to_owned(),
+            false => comparisons.join(" &&\n"),
+        };
+        code.add_block(
+            FunctionBuilder::new(
+                &readonly_modifiers(struct_def, false),
+                "bool",
+                "Equals",
+                FunctionType::ExpressionBody,
+            )
+            .add_comment(
+                "summary",
+                format!(r#"Indicates whether this struct is equal to another <see cref="{escaped_identifier}" />."#),
+            )
+            .add_parameter(
+                &parameter_type(struct_def),
+                "other",
+                None,
+                Some("The struct to compare with this struct.".to_owned()),
+            )
+            .add_comment(
+                "returns",
+                "<see langword=\"true\" /> if both structs are equal; otherwise, <see langword=\"false\" />.",
+            )
+            .set_body(equals_body.into())
+            .build(),
+        );
+    }
+
+    code.add_block(
+        FunctionBuilder::new(
+            &readonly_modifiers(struct_def, true),
+            "bool",
+            "Equals",
+            FunctionType::ExpressionBody,
+        )
+        .set_inherit_doc(true)
+        .add_parameter("object?", "obj", None, None)
+        .set_body(format!("obj is {escaped_identifier} other && Equals(other)").into())
+        .build(),
+    );
+
+    if !skip_tagged && !struct_def.has_attribute::<CsStableHash>() {
+        let mut hash_code_body = CodeBlock::default();
+        writeln!(hash_code_body, "var hash = new global::System.HashCode();");
+        for field in &fields {
+            writeln!(hash_code_body, "hash.Add(this.{});", field.field_name());
+        }
+        writeln!(hash_code_body, "return hash.ToHashCode();");
+
+        code.add_block(
+            FunctionBuilder::new(
+                &readonly_modifiers(struct_def, true),
+                "int",
+                "GetHashCode",
+                FunctionType::BlockBody,
+            )
+            .add_comment("summary", "Computes the hash code of this struct.")
+            .add_comment("returns", "The hash code of this struct.")
+            .set_body(hash_code_body)
+            .build(),
+        );
+    }
+
+    // Since classes can be null, their operators use the default equality comparer, which handles null operands.
+    let equals = match is_class {
+        true => format!(
+            "global::System.Collections.Generic.EqualityComparer<{escaped_identifier}>.Default.Equals(left, right)"
+        ),
+        false => "left.Equals(right)".to_owned(),
+    };
+    for (operator, body) in [("==", equals.clone()), ("!=", format!("!({equals})"))] {
+        code.add_block(
+            FunctionBuilder::new(
+                &(struct_def.access_modifier().to_owned() + " static"),
+                "bool",
+                &format!("operator {operator}"),
+                FunctionType::ExpressionBody,
+            )
+            .add_comment(
+                "summary",
+                format!("Compares two <see cref=\"{escaped_identifier}\" /> with the <c>{operator}</c> operator."),
+            )
+            .add_parameter(
+                &parameter_type(struct_def),
+                "left",
+                None,
+                Some("The left operand.".to_owned()),
+            )
+            .add_parameter(
+                &parameter_type(struct_def),
+                "right",
+                None,
+                Some("The right operand.".to_owned()),
+            )
+            .set_body(body.into())
+            .build(),
+        );
+    }
+
+    code
+}
+
+/// Generates an `Equals` overload that takes the other struct by `in` reference, so comparing two structs doesn't copy
+/// either of them. It compares the same fields as the struct's own `Equals` method.
+fn fast_equals(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let namespace = struct_def.namespace();
+    let skip_tagged = struct_def.has_attribute::<CsValueEqualitySkipTagged>();
+    let fields = struct_def
+        .fields()
+        .into_iter()
+        .filter(|field| !(skip_tagged && field.is_tagged()))
+        .collect::<Vec<_>>();
+
+    let comparisons = field_comparisons(&fields, &namespace);
+    let equals_body = match comparisons.is_empty() {
+        true => "true".to_owned(),
+        false => comparisons.join(" &&\n"),
+    };
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, false),
+        "bool",
+        "Equals",
+        FunctionType::ExpressionBody,
+    )
+    .add_comment(
+        "summary",
+        format!(r#"Indicates whether this struct is equal to another <see cref="{escaped_identifier}" />, without copying either struct."#),
+    )
+    .add_comment(
+        "remarks",
+        "Call this method with the <c>in</c> modifier to select it over the <c>Equals</c> method synthesized for record structs.",
+    )
+    .add_parameter(
+        &format!("in {escaped_identifier}"),
+        "other",
+        None,
+        Some("The struct to compare with this struct.".to_owned()),
+    )
+    .add_comment(
+        "returns",
+        "<see langword=\"true\" /> if both structs are equal; otherwise, <see langword=\"false\" />.",
+    )
+    .set_body(equals_body.into())
+    .build()
+}
+
+/// Returns an expression comparing each of the provided fields of this struct with the same field of `other`.
+fn field_comparisons(fields: &[&Field], namespace: &str) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field| {
+            format!(
+                "global::System.Collections.Generic.EqualityComparer<{field_type}>.Default.Equals(this.{field_name}, other.{field_name})",
+                field_type = field.property_type_string(namespace),
+                field_name = field.field_name(),
+            )
+        })
+        .collect()
+}
+
+/// Generates a `GetHashCode` override that returns the same hash code across processes. Strings are hashed with FNV-1a
+/// instead of the randomized string hash, sequences are hashed element by element, and other fields use their own
+/// hash code.
+fn stable_hash_code(struct_def: &Struct) -> CodeBlock {
+    let mut field_hashes = CodeBlock::default();
+    for field in struct_def.fields() {
+        write_stable_hash(
+            &mut field_hashes,
+            field.data_type(),
+            &format!("this.{}", field.field_name()),
+            0,
+        );
+    }
+
+    let mut body = CodeBlock::default();
+    writeln!(
+        body,
+        "\
+unchecked
+{{
+    int hash = 17;
+    {field_hashes}
+    return hash;
+}}",
+        field_hashes = field_hashes.indent(),
+    );
+    if struct_def
+        .fields()
+        .iter()
+        .any(|field| contains_strings(field.data_type()))
+    {
+        writeln!(
+            body,
+            "
+static int GetStableHashCode(string? value)
+{{
+    if (value is null)
+    {{
+        return 0;
+    }}
+    uint hash = 2166136261;
+    foreach (char c in value)
+    {{
+        hash = unchecked((hash ^ c) * 16777619);
+    }}
+    return unchecked((int)hash);
+}}",
+        );
+    }
+
+    FunctionBuilder::new(
+        &readonly_modifiers(struct_def, true),
+        "int",
+        "GetHashCode",
+        FunctionType::BlockBody,
+    )
+    .add_comment(
+        "summary",
+        "Computes the hash code of this struct. Unlike the default hash code, it's the same across processes.",
+    )
+    .add_comment("returns", "The hash code of this struct.")
+    .set_body(body)
+    .build()
+}
+
+/// Writes the statements that combine the hash code of `value` into the `hash` variable. Each nested sequence gets its
+/// own element variable, which is distinguished by `depth`.
+fn write_stable_hash(code: &mut CodeBlock, type_ref: &TypeRef, value: &str, depth: usize) {
+    match type_ref.concrete_type() {
+        Types::Primitive(Primitive::String) => writeln!(code, "hash = (hash * 31) + GetStableHashCode({value});"),
+        Types::Sequence(sequence_def) => {
+            let element = match depth {
+                0 => "element".to_owned(),
+                _ => format!("element{depth}"),
+            };
+            let mut element_hash = CodeBlock::default();
+            write_stable_hash(&mut element_hash, &sequence_def.element_type, &element, depth + 1);
+
+            let elements = match type_ref.is_read_only_memory() {
+                true => format!("{value}.Span"),
+                false => value.to_owned(),
+            };
+            let mut hash_loop: CodeBlock = format!(
+                "\
+foreach (var {element} in {elements})
+{{
+    {element_hash}
+}}",
+                element_hash = element_hash.indent(),
+            )
+            .into();
+            if type_ref.is_optional {
+                hash_loop = format!(
+                    "\
+if ({value} is not null)
+{{
+    {hash_loop}
+}}",
+                    hash_loop = hash_loop.indent(),
+                )
+                .into();
+            }
+            code.writeln(&hash_loop);
+        }
+        _ if type_ref.is_optional => writeln!(code, "hash = (hash * 31) + ({value}?.GetHashCode() ?? 0);"),
+        _ => writeln!(code, "hash = (hash * 31) + {value}.GetHashCode();"),
+    }
+}
+
+/// Returns true if the provided type is a string, or a sequence that (transitively) holds strings.
+fn contains_strings(type_ref: &TypeRef) -> bool {
+    match type_ref.concrete_type() {
+        Types::Primitive(Primitive::String) => true,
+        Types::Sequence(sequence_def) => contains_strings(&sequence_def.element_type),
+        _ => false,
+    }
+}
+
+/// Generates a `CompareTo` method that compares the struct's fields in declaration order, along with the comparison
+/// operators. Strings are compared ordinally.
+fn comparison(struct_def: &Struct) -> CodeBlock {
+    let escaped_identifier = struct_def.escape_identifier();
+    let namespace = struct_def.namespace();
+
+    let is_class = struct_def.has_attribute::<CsClass>();
+
+    let mut body = CodeBlock::default();
+    if is_class {
+        // Following the convention of the .NET comparers, null is less than any other value.
+        writeln!(
+            body,
+            "\
+if (other is null)
+{{
+    return 1;
+}}"
+        );
+    }
+    writeln!(body, "int result;");
+    for field in struct_def.fields() {
+        let field_name = field.field_name();
+        let comparison = match field.data_type().concrete_type() {
+            Types::Primitive(Primitive::String) => format!("string.CompareOrdinal(this.{field_name}, other.{field_name})"),
+            _ => format!(
+                "global::System.Collections.Generic.Comparer<{}>.Default.Compare(this.{field_name}, other.{field_name})",
+                field.data_type().field_type_string(&namespace),
+            ),
+        };
+        writeln!(
+            body,
+            "\
+result = {comparison};
+if (result != 0)
+{{
+    return result;
+}}",
+        );
+    }
+    writeln!(body, "return 0;");
+
+    let mut code = CodeBlock::default();
+    code.add_block(
+        FunctionBuilder::new(
+            &readonly_modifiers(struct_def, false),
+            "int",
+            "CompareTo",
+            FunctionType::BlockBody,
+        )
+        .set_inherit_doc(true)
+        .add_parameter(&parameter_type(struct_def), "other", None, None)
+        .set_body(body)
+        .build(),
+    );
+
+    // Since classes can be null, their operators use the default comparer, which handles null operands.
+    let compare = match is_class {
+        true => {
+            format!("global::System.Collections.Generic.Comparer<{escaped_identifier}>.Default.Compare(left, right)")
+        }
+        false => "left.CompareTo(right)".to_owned(),
+    };
+
+    for operator in ["<", ">", "<=", ">="] {
+        code.add_block(
+            FunctionBuilder::new(
+                &(struct_def.access_modifier().to_owned() + " static"),
+                "bool",
+                &format!("operator {operator}"),
+                FunctionType::ExpressionBody,
+            )
+            .add_comment(
+                "summary",
+                format!("Compares two <see cref=\"{escaped_identifier}\" /> with the <c>{operator}</c> operator."),
+            )
+            .add_parameter(
+                &parameter_type(struct_def),
+                "left",
+                None,
+                Some("The left operand.".to_owned()),
+            )
+            .add_parameter(
+                &parameter_type(struct_def),
+                "right",
+                None,
+                Some("The right operand.".to_owned()),
+            )
+            .set_body(format!("{compare} {operator} 0").into())
+            .build(),
+        );
+    }
+
+    code
+}
+
+/// Generates an expression for encoding or decoding the fields of a struct.
+/// It checks which encodings this struct supports, and only generates code for the necessary encodings.
+fn generate_encoding_blocks(
+    fields: &[&Field],
+    encodings: SupportedEncodings,
+    encoding_fn: fn(&[&Field], Encoding) -> Vec<CodeBlock>,
+    encoding_source: &'static str,
+) -> CodeBlock {
+    match encodings[..] {
+        [] => unreachable!("No supported encodings"),
+        [encoding] => {
+            let mut code = CodeBlock::default();
+            write_blocks(&mut code, &encoding_fn(fields, encoding));
+            code
+        }
+        _ => {
+            let slice1_blocks = encoding_fn(fields, Encoding::Slice1);
+            let slice2_blocks = encoding_fn(fields, Encoding::Slice2);
+
+            // The encoding blocks are only empty for empty structs. But `Slice1` doesn't support empty structs.
+            // So this branch of the match statement is never hit, since it's for structs that support both encodings.
+            // Note that the first block of each encoding is for the bit sequence, the remaining blocks are for the fields.
+            assert!(slice1_blocks.len() > 1 && slice2_blocks.len() > 1);
+
+            merge_encoding_blocks(&slice1_blocks, &slice2_blocks, encoding_source)
+        }
+    }
+}
+
+/// Merges the `Slice1` and `Slice2` code blocks of a struct's fields into a single code block.
+/// Blocks which are identical at the start and end of both encodings are only written once, and only the blocks in the
+/// middle that differ are wrapped in an `if` statement that checks the encoding being used.
+fn merge_encoding_blocks(slice1_blocks: &[CodeBlock], slice2_blocks: &[CodeBlock], encoding_source: &str) -> CodeBlock {
+    let slice1_strings = slice1_blocks.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let slice2_strings = slice2_blocks.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    let prefix_length = slice1_strings
+        .iter()
+        .zip(&slice2_strings)
+        .take_while(|(slice1, slice2)| slice1 == slice2)
+        .count();
+    let suffix_length = slice1_strings[prefix_length..]
+        .iter()
+        .rev()
+        .zip(slice2_strings[prefix_length..].iter().rev())
+        .take_while(|(slice1, slice2)| slice1 == slice2)
+        .count();
+
+    let mut slice1_block = CodeBlock::default();
+    write_blocks(
+        &mut slice1_block,
+        &slice1_blocks[prefix_length..slice1_blocks.len() - suffix_length],
+    );
+    let mut slice2_block = CodeBlock::default();
+    write_blocks(
+        &mut slice2_block,
+        &slice2_blocks[prefix_length..slice2_blocks.len() - suffix_length],
+    );
+
+    let mut code = CodeBlock::default();
+    write_blocks(&mut code, &slice2_blocks[..prefix_length]);
+    // When only one of the encodings has blocks in the middle, we check for this encoding without an empty `else`.
+    match (slice1_block.is_empty(), slice2_block.is_empty()) {
+        (true, true) => {}
+        (false, true) => write!(
+            code,
+            "\
+if ({encoding_source}.Encoding == SliceEncoding.Slice1)
+{{
+    {slice1_block}
+}}
+",
+            slice1_block = slice1_block.indent(),
+        ),
+        (true, false) => write!(
+            code,
+            "\
+if ({encoding_source}.Encoding != SliceEncoding.Slice1)
+{{
+    {slice2_block}
+}}
+",
+            slice2_block = slice2_block.indent(),
+        ),
+        (false, false) => write!(
+            code,
+            "\
+if ({encoding_source}.Encoding == SliceEncoding.Slice1)
+{{
+    {slice1_block}
+}}
+else // Slice2
+{{
+    {slice2_block}
+}}
+",
+            slice1_block = slice1_block.indent(),
+            slice2_block = slice2_block.indent(),
+        ),
+    }
+    write_blocks(&mut code, &slice2_blocks[slice2_blocks.len() - suffix_length..]);
+    code
+}
+
+/// Writes each of the provided blocks on its own line.
+fn write_blocks(code: &mut CodeBlock, blocks: &[CodeBlock]) {
+    for block in blocks {
+        code.writeln(block);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attribute_tests::compile_for_ast;
+    use crate::cs_options::TargetFramework;
+    use test_case::test_case;
+
+    #[test]
+    fn parsable_is_implemented_by_structs_wrapping_a_single_numeric_field() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::formattable]
+            compact struct Celsius {
+                degrees: float64
+            }
+
+            [cs::formattable]
+            compact struct Money {
+                currency: string
+                amount: float64
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let celsius = ast.find_element::<Struct>("Test::Celsius").unwrap();
+        let money = ast.find_element::<Struct>("Test::Money").unwrap();
+
+        // Act
+        let celsius_code = generate_struct(celsius, &CsOptions::default()).to_string();
+        let money_code = generate_struct(money, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(celsius_code.contains(
+            "public partial record struct Celsius : global::System.ISpanFormattable, global::System.IParsable<Celsius>"
+        ));
+        assert!(celsius_code.contains(
+            "\
+    public static Celsius Parse(
+        string s,
+        global::System.IFormatProvider? provider) =>
+        new(double.Parse(s, provider));"
+        ));
+        assert!(celsius_code.contains(
+            "\
+    public static bool TryParse(
+        [global::System.Diagnostics.CodeAnalysis.NotNullWhen(true)] string? s,
+        global::System.IFormatProvider? provider,
+        [global::System.Diagnostics.CodeAnalysis.MaybeNullWhen(false)] out Celsius result)
+    {
+        if (double.TryParse(s, provider, out double value))
+        {
+            result = new(value);
+            return true;
+        }
+        result = default;
+        return false;
+    }"
+        ));
+        assert!(!money_code.contains("IParsable"));
+        assert!(!money_code.contains("Parse("));
+    }
+
+    #[test_case("int32", "new(left.Value + right.Value)"; "int")]
+    #[test_case("uint8", "new((byte)(left.Value + right.Value))"; "promoted byte")]
+    fn numeric_structs_forward_arithmetic_operators_to_their_field(field_type: &str, expected_addition: &str) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            [cs::numeric]
+            compact struct Amount {{
+                value: {field_type}
+            }}
+            "
+        );
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::Amount").unwrap();
+
+        // Act
+        let code = arithmetic_operators(struct_def).to_string();
+
+        // Assert
+        let expected = format!(
+            "\
+/// <summary>Adds two <see cref=\"Amount\" /> values.</summary>
+/// <param name=\"left\">The left operand.</param>
+/// <param name=\"right\">The right operand.</param>
+/// <returns>The result of the operation.</returns>
+public static Amount operator +(
+    Amount left,
+    Amount right) =>
+    {expected_addition};"
+        );
+        assert!(code.starts_with(&expected));
+        for operator in ["-", "*", "/"] {
+            assert!(code.contains(&format!("public static Amount operator {operator}(")));
+        }
+    }
+
+    #[test]
+    fn numeric_structs_implement_the_operator_interfaces() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::numeric]
+            compact struct Amount {
+                value: float64
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::Amount").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        for interface in [
+            "IAdditionOperators",
+            "ISubtractionOperators",
+            "IMultiplyOperators",
+            "IDivisionOperators",
+        ] {
+            assert!(code.contains(&format!("global::System.Numerics.{interface}<Amount, Amount, Amount>")));
+        }
+    }
+
+    #[test]
+    fn deconstruct_matches_main_constructor() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::deconstruct]
+            struct MyStruct {
+                a: int32
+                b: string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = deconstruct(struct_def).to_string();
+
+        // Assert
+        let expected = "\
+/// <summary>Deconstructs this struct into its fields.</summary>
+public readonly void Deconstruct(
+    out int a,
+    out string b)
+{
+    a = this.A;
+    b = this.B;
+}";
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn deep_clone_copies_mutable_collections() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::deepClone]
+            struct MyStruct {
+                a: int32
+                b: Sequence<int32>
+                c: Dictionary<string, int32>?
+                [cs::readonlyList] d: Sequence<int32>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = deep_clone(struct_def).to_string();
+
+        // Assert
+        let expected = "\
+/// <summary>Creates a new instance of <see cref=\"MyStruct\" /> with copies of the fields of this instance.</summary>
+/// <remarks>Mutable collections are copied into new collections, but their elements are not cloned.</remarks>
+/// <returns>The new instance.</returns>
+public readonly MyStruct DeepClone() =>
+    new(
+        this.A,
+        global::System.Linq.Enumerable.ToArray(this.B),
+        this.C is null ? null : new global::System.Collections.Generic.Dictionary<string, int>(this.C),
+        this.D);";
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn field_tags_map_tagged_fields_to_their_tags() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+                tag(1) b: string?
+                tag(5) c: int32?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = field_tags(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Gets the Slice tags of the tagged fields of this struct, keyed by the names of their C# properties.</summary>
+public static readonly global::System.Collections.Generic.IReadOnlyDictionary<string, int> FieldTags =
+    global::System.Collections.Frozen.FrozenDictionary.ToFrozenDictionary(
+        new global::System.Collections.Generic.Dictionary<string, int>
+        {
+            ["B"] = 1,
+            ["C"] = 5,
+        });"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn field_tags_of_structs_without_tagged_fields_are_empty() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = field_tags(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(
+            code.ends_with("FieldTags =\n    global::System.Collections.Frozen.FrozenDictionary<string, int>.Empty;")
+        );
+    }
+
+    #[test]
+    fn field_tags_of_compact_structs_are_empty() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::fieldTags]
+            compact struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = "FieldTags =\n        global::System.Collections.Frozen.FrozenDictionary<string, int>.Empty;";
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn field_tags_are_only_generated_for_structs_marked_with_field_tags() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                tag(1) a: int32?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(!code.contains("FieldTags"));
+    }
+
+    #[test]
+    fn custom_default_passes_the_provided_defaults_to_the_main_constructor() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::customDefault(\"name = string.Empty\", \"count = 1\")]
+            struct MyStruct {
+                name: string
+                flag: bool
+                count: int32
+                tag(1) b: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let custom_default = struct_def.find_attribute::<CsCustomDefault>().unwrap();
+
+        // Act
+        let code = custom_default_field(struct_def, custom_default).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>The default value of <see cref="MyStruct" />.</summary>
+/// <remarks>This field is initialized with the default values provided in the Slice definition. Unlike this field,
+/// <c>default(MyStruct)</c> still zero-initializes all fields.</remarks>
+public static readonly MyStruct Default = new(string.Empty, default, 1, default);"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn builder_passes_its_values_to_the_main_constructor() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::builder]
+            struct MyStruct {
+                /// The A field.
+                a: int32
+                tag(1) b: string?
+                c: bool?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = builder_class(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Builds instances of <see cref="MyStruct" /> one field at a time.</summary>
+public sealed class Builder
+{
+    private string? _b;
+    private bool? _c;
+
+    /// <summary>Sets the value of <see cref="MyStruct.B" />.</summary>
+    /// <param name="value">The value of the field.</param>
+    /// <returns>This builder.</returns>
+    public Builder WithB(string? value)
+    {
+        _b = value;
+        return this;
+    }
+
+    /// <summary>Sets the value of <see cref="MyStruct.C" />.</summary>
+    /// <param name="value">The value of the field.</param>
+    /// <returns>This builder.</returns>
+    public Builder WithC(bool? value)
+    {
+        _c = value;
+        return this;
+    }
+
+    /// <summary>Constructs a new instance of <see cref="MyStruct" /> from the values set on this builder.</summary>
+    /// <param name="a">The A field.</param>
+    /// <returns>The new instance.</returns>
+    public MyStruct Build(int a) =>
+        new MyStruct(a, _b, _c);
+}"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn only_fields_marked_with_intern_are_interned_when_decoded() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                [cs::intern]
+                a: string
+                b: string
+                [cs::intern(\"Pool.Shared\")]
+                tag(1) c: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("string.Intern(decoder.DecodeString()),\n"));
+        assert!(code.contains("decoder.DecodeString(),\n"));
+        assert!(code.contains("(ref SliceDecoder decoder) => (string?)Pool.Shared.GetOrAdd(decoder.DecodeString())"));
+        assert_eq!(code.matches("string.Intern").count(), 1);
+
+        // Interning has no effect on encoding.
+        assert!(code.contains("encoder.EncodeString(this.A);"));
+    }
+
+    #[test]
+    fn identical_encoding_blocks_are_written_once() {
+        // Arrange
+        let slice1_blocks = ["", "encoder.EncodeInt32(this.A);", "encoder.EncodeString(this.B);"].map(CodeBlock::from);
+        let slice2_blocks = slice1_blocks.clone();
+
+        // Act
+        let code = merge_encoding_blocks(&slice1_blocks, &slice2_blocks, "encoder").to_string();
+
+        // Assert
+        let expected = "\
+encoder.EncodeInt32(this.A);
+encoder.EncodeString(this.B);";
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn different_encoding_blocks_are_all_wrapped() {
+        // Arrange
+        let slice1_blocks = ["", "encoder.EncodeA1();", "encoder.EncodeB1();"].map(CodeBlock::from);
+        let slice2_blocks = [
+            "var bitSequenceWriter = encoder.GetBitSequenceWriter(1);",
+            "encoder.EncodeA2();",
+            "encoder.EncodeB2();",
+        ]
+        .map(CodeBlock::from);
+
+        // Act
+        let code = merge_encoding_blocks(&slice1_blocks, &slice2_blocks, "encoder").to_string();
+
+        // Assert
+        let expected = "\
+if (encoder.Encoding == SliceEncoding.Slice1)
+{
+    encoder.EncodeA1();
+    encoder.EncodeB1();
+}
+else // Slice2
+{
+    var bitSequenceWriter = encoder.GetBitSequenceWriter(1);
+    encoder.EncodeA2();
+    encoder.EncodeB2();
+}";
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn only_differing_encoding_blocks_are_wrapped() {
+        // Arrange
+        let slice1_blocks = [
+            "",
+            "this.A = decoder.DecodeA();",
+            "this.B = decoder.DecodeB1();",
+            "this.C = decoder.DecodeC();",
+        ]
+        .map(CodeBlock::from);
+        let slice2_blocks = [
+            "",
+            "this.A = decoder.DecodeA();",
+            "this.B = decoder.DecodeB2();",
+            "this.C = decoder.DecodeC();",
+        ]
+        .map(CodeBlock::from);
+
+        // Act
+        let code = merge_encoding_blocks(&slice1_blocks, &slice2_blocks, "decoder").to_string();
+
+        // Assert
+        let expected = "\
+this.A = decoder.DecodeA();
+if (decoder.Encoding == SliceEncoding.Slice1)
+{
+    this.B = decoder.DecodeB1();
+}
+else // Slice2
+{
+    this.B = decoder.DecodeB2();
+}
+this.C = decoder.DecodeC();";
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn encoding_blocks_of_a_single_encoding_are_wrapped_without_an_else() {
+        // Arrange
+        let slice1_blocks = ["", "encoder.EncodeA();", "encoder.EncodeB();"].map(CodeBlock::from);
+        let slice2_blocks = [
+            "var bitSequenceWriter = encoder.GetBitSequenceWriter(1);",
+            "encoder.EncodeA();",
+            "encoder.EncodeB();",
+        ]
+        .map(CodeBlock::from);
+
+        let slice1_tail_blocks = ["encoder.EncodeA();", "encoder.EncodeB();"].map(CodeBlock::from);
+        let slice2_tail_blocks = ["encoder.EncodeA();"].map(CodeBlock::from);
+
+        // Act
+        let slice2_only = merge_encoding_blocks(&slice1_blocks, &slice2_blocks, "encoder").to_string();
+        let slice1_only = merge_encoding_blocks(&slice1_tail_blocks, &slice2_tail_blocks, "encoder").to_string();
+
+        // Assert
+        let expected_slice2_only = "\
+if (encoder.Encoding != SliceEncoding.Slice1)
+{
+    var bitSequenceWriter = encoder.GetBitSequenceWriter(1);
+}
+encoder.EncodeA();
+encoder.EncodeB();";
+        let expected_slice1_only = "\
+encoder.EncodeA();
+if (encoder.Encoding == SliceEncoding.Slice1)
+{
+    encoder.EncodeB();
+}";
+        assert_eq!(slice2_only, expected_slice2_only);
+        assert_eq!(slice1_only, expected_slice1_only);
+    }
+
+    #[test]
+    fn encode_options_overload_writes_tag_end_marker_once() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::encodeOptions]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("Encode(ref encoder, maxEncodedByteCount: -1);"));
+        assert_eq!(code.matches("Slice2Definitions.TagEndMarker").count(), 1);
+    }
+
+    #[test]
+    fn to_string_prints_field_names_and_values() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::toString]
+            struct MyStruct {
+                a: int32
+                b: string
+                c: int32?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = to_string(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Returns a string that represents this struct, including the names and values of its fields.</summary>
+/// <returns>A string that represents this struct.</returns>
+public override readonly string ToString()
+{
+    var builder = new global::System.Text.StringBuilder("MyStruct {");
+    builder.Append(" A = ").Append(this.A);
+    builder.Append(", B = ").Append(this.B is null ? "null" : $"\"{this.B}\"");
+    builder.Append(", C = ").Append(this.C?.ToString() ?? "null");
+    builder.Append(" }");
+    return builder.ToString();
+}"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn equality_skips_tagged_fields() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::valueEqualitySkipTagged]
+            struct MyStruct {
+                a: int32
+                tag(1) b: string?
+                c: string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = equality_skip_tagged(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Indicates whether this struct is equal to another <see cref="MyStruct" />. Tagged fields are not compared.</summary>
+/// <param name="other">The struct to compare with this struct.</param>
+/// <returns><see langword="true" /> if the non-tagged fields of both structs are equal; otherwise, <see langword="false" />.</returns>
+public readonly bool Equals(MyStruct other) =>
+    global::System.Collections.Generic.EqualityComparer<int>.Default.Equals(this.A, other.A) &&
+    global::System.Collections.Generic.EqualityComparer<string>.Default.Equals(this.C, other.C);
+
+/// <summary>Computes the hash code of this struct. Tagged fields are not included.</summary>
+/// <returns>The hash code of this struct.</returns>
+public override readonly int GetHashCode()
+{
+    var hash = new global::System.HashCode();
+    hash.Add(this.A);
+    hash.Add(this.C);
+    return hash.ToHashCode();
+}"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn try_decode_resets_decoder_on_failure() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::tryDecode]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = try_decode(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Tries to decode a <see cref="MyStruct" /> from a Slice decoder.</summary>
+/// <remarks>If the decoding fails, the decoder is reset to the position it had before this method was called.</remarks>
+/// <param name="decoder">The Slice decoder.</param>
+/// <param name="value">When this method returns <see langword="true" />, contains the decoded struct; otherwise, contains <see langword="default" />.</param>
+/// <returns><see langword="true" /> if the struct was successfully decoded; otherwise, <see langword="false" />.</returns>
+public static bool TryDecode(
+    ref SliceDecoder decoder,
+    out MyStruct value)
+{
+    SliceDecoder snapshot = decoder;
+    try
+    {
+        value = new MyStruct(ref decoder);
+        return true;
+    }
+    catch (global::System.IO.InvalidDataException)
+    {
+        decoder = snapshot;
+        value = default;
+        return false;
+    }
+}"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn encode_to_array_defaults_to_the_only_supported_encoding() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::encodeToArray]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = encode_to_array(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Encodes this struct into a new byte array.</summary>
+/// <param name="encoding">The encoding to encode this struct with.</param>
+/// <returns>A byte array holding the encoded struct.</returns>
+/// <exception cref="global::System.NotSupportedException">Thrown if this struct doesn't support the provided encoding.</exception>
+public readonly byte[] EncodeToArray(SliceEncoding encoding = SliceEncoding.Slice2)
+{
+    if (encoding != SliceEncoding.Slice2)
+    {
+        throw new global::System.NotSupportedException(
+            $"The struct 'MyStruct' cannot be encoded with the {encoding} encoding.");
+    }
+    var bufferWriter = new global::System.Buffers.ArrayBufferWriter<byte>();
+    var encoder = new SliceEncoder(bufferWriter, encoding);
+    Encode(ref encoder);
+    return bufferWriter.WrittenSpan.ToArray();
+}"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn try_encode_encodes_into_a_rented_buffer() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::tryEncode]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = try_encode(struct_def).to_string();
+
+        // Assert
+        let expected = "\
+public readonly bool TryEncode(
+    global::System.Span<byte> destination,
+    out int bytesWritten,
+    SliceEncoding encoding = SliceEncoding.Slice2)
+{
+    if (encoding != SliceEncoding.Slice2)
+    {
+        throw new global::System.NotSupportedException(
+            $\"The struct 'MyStruct' cannot be encoded with the {encoding} encoding.\");
+    }
+    // The rented buffer is a little larger than the destination, since the encoder requests 8 bytes to encode a varint,
+    // even when it writes fewer bytes.
+    byte[] buffer = global::System.Buffers.ArrayPool<byte>.Shared.Rent(destination.Length + 8);
+    try
+    {
+        var bufferWriter = new TryEncodeBufferWriter(buffer);
+        var encoder = new SliceEncoder(bufferWriter, encoding);
+        Encode(ref encoder);
+        if (bufferWriter.HasOverflowed || bufferWriter.WrittenSpan.Length > destination.Length)
+        {
+            bytesWritten = 0;
+            return false;
+        }
+        bufferWriter.WrittenSpan.CopyTo(destination);
+        bytesWritten = bufferWriter.WrittenSpan.Length;
+        return true;
+    }
+    finally
+    {
+        global::System.Buffers.ArrayPool<byte>.Shared.Return(buffer);
+    }
+}";
+        assert!(code.ends_with(expected));
+    }
+
+    #[test]
+    fn try_encode_buffer_writer_is_private_and_never_throws() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::tryEncode]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(
+            code.contains("private sealed class TryEncodeBufferWriter : global::System.Buffers.IBufferWriter<byte>")
+        );
+        assert!(!code.contains("throw new global::System.InvalidOperationException"));
+        assert!(!code.contains("catch"));
+    }
+
+    #[test]
+    fn explicit_constructors_only_generates_plain_structs() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+                b: string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            explicit_constructors_only: true,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        assert!(code.contains("public partial struct MyStruct : global::System.IEquatable<MyStruct>"));
+        assert!(!code.contains("record"));
+        assert!(code.contains("public MyStruct(\n        int a,\n        string b)"));
+        assert!(code.contains("public readonly bool Equals(MyStruct other) =>"));
+        assert!(code.contains(
+            "public override readonly bool Equals(object? obj) =>\n        obj is MyStruct other && Equals(other);"
+        ));
+        assert!(code.contains("public override readonly int GetHashCode()"));
+        assert!(code.contains("public override readonly string ToString()"));
+        assert!(code.contains(
+            "public static bool operator ==(\n        MyStruct left,\n        MyStruct right) =>\n        left.Equals(right);"
+        ));
+        assert!(code.contains(
+            "public static bool operator !=(\n        MyStruct left,\n        MyStruct right) =>\n        !(left.Equals(right));"
+        ));
+    }
+
+    #[test]
+    fn explicit_constructors_only_reuses_stable_hash_and_skip_tagged_equality() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::class]
+            [cs::stableHash]
+            struct MyClass {
+                a: int32
+            }
+
+            [cs::valueEqualitySkipTagged]
+            struct MyStruct {
+                a: int32
+                tag(1) b: int32?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let class_def = ast.find_element::<Struct>("Test::MyClass").unwrap();
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            explicit_constructors_only: true,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let class_code = generate_struct(class_def, &options).to_string();
+        let struct_code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        assert!(class_code.contains("public sealed partial class MyClass : global::System.IEquatable<MyClass>"));
+        assert_eq!(class_code.matches("int GetHashCode()").count(), 1);
+        assert!(class_code.contains("Unlike the default hash code, it's the same across processes."));
+        assert!(class_code.contains("EqualityComparer<MyClass>.Default.Equals(left, right)"));
+        assert_eq!(struct_code.matches("bool Equals(MyStruct other)").count(), 1);
+        assert_eq!(struct_code.matches("int GetHashCode()").count(), 1);
+        assert!(struct_code.contains("Tagged fields are not compared."));
+    }
+
+    #[test]
+    fn data_contract_orders_data_members_like_the_slice_fields() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::dataContract]
+            struct MyStruct {
+                a: int32
+                tag(1) b: string?
+                c: bool
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(
+            code.contains("[global::System.Runtime.Serialization.DataContract]\npublic partial record struct MyStruct")
+        );
+        assert!(code
+            .contains("[global::System.Runtime.Serialization.DataMember(Order = 0)]\n    public int A { get; set; }"));
+        assert!(code.contains(
+            "[global::System.Runtime.Serialization.DataMember(Order = 1, IsRequired = false)]\n    public string? B { get; set; }"
+        ));
+        assert!(code
+            .contains("[global::System.Runtime.Serialization.DataMember(Order = 2)]\n    public bool C { get; set; }"));
+    }
+
+    #[test]
+    fn nullable_attributes_match_the_nullability_of_fields() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: string
+                b: int32
+                tag(1) c: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            emit_nullable_context_per_member: true,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        assert!(code.contains("[global::System.Diagnostics.CodeAnalysis.NotNull]\n    public string A { get; set; }"));
+        assert!(code.contains("public int B { get; set; }"));
+        assert!(code.contains(
+            "\
+    [global::System.Diagnostics.CodeAnalysis.AllowNull]
+    [global::System.Diagnostics.CodeAnalysis.MaybeNull]
+    public string? C { get; set; }"
+        ));
+        assert_eq!(code.matches("CodeAnalysis.").count(), 3);
+    }
+
+    #[test]
+    fn readonly_is_inferred_when_all_fields_are_readonly() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct InferredStruct {
+                [cs::readonly] a: int32
+                [cs::init] b: string
+            }
+
+            [cs::mutable]
+            struct MutableStruct {
+                [cs::readonly] a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let inferred_struct = ast.find_element::<Struct>("Test::InferredStruct").unwrap();
+        let mutable_struct = ast.find_element::<Struct>("Test::MutableStruct").unwrap();
+
+        // Act
+        let inferred_code = generate_struct(inferred_struct, &CsOptions::default()).to_string();
+        let mutable_code = generate_struct(mutable_struct, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(inferred_code.contains("public readonly partial record struct InferredStruct"));
+        assert!(inferred_code.contains("because all of its fields are readonly"));
+        assert!(mutable_code.contains("public partial record struct MutableStruct"));
+    }
+
+    #[test]
+    fn formattable_to_string_forwards_to_the_numeric_field() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::formattable]
+            compact struct Money {
+                currency: string
+                amount: float64
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::Money").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public partial record struct Money : global::System.ISpanFormattable"));
+        assert!(code.contains(
+            "\
+    public readonly string ToString(
+        string? format,
+        global::System.IFormatProvider? formatProvider) =>
+        this.Amount.ToString(format, formatProvider);"
+        ));
+        assert!(code.contains(
+            "\
+    public readonly bool TryFormat(
+        global::System.Span<char> destination,
+        out int charsWritten,
+        global::System.ReadOnlySpan<char> format,
+        global::System.IFormatProvider? provider) =>
+        this.Amount.TryFormat(destination, out charsWritten, format, provider);"
+        ));
+    }
+
+    #[test]
+    fn compare_to_compares_fields_in_order() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::comparable]
+            struct MyStruct {
+                a: int32
+                b: string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = comparison(struct_def).to_string();
+
+        // Assert
+        let expected = r#"/// <inheritdoc/>
+public readonly int CompareTo(MyStruct other)
+{
+    int result;
+    result = global::System.Collections.Generic.Comparer<int>.Default.Compare(this.A, other.A);
+    if (result != 0)
+    {
+        return result;
+    }
+    result = string.CompareOrdinal(this.B, other.B);
+    if (result != 0)
+    {
+        return result;
+    }
+    return 0;
+}"#;
+        assert!(code.starts_with(expected));
+        assert!(code.contains("public static bool operator <=(\n    MyStruct left,\n    MyStruct right) =>\n    left.CompareTo(right) <= 0;"));
+    }
+
+    #[test]
+    fn trailing_optional_fields_have_default_values() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: string?
+                b: int32
+                c: string?
+                tag(1) d: int32?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = "\
+    public MyStruct(
+        string? a,
+        int b,
+        string? c = null,
+        int? d = null)";
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn class_attribute_generates_record_class() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::class]
+            [cs::valueEqualitySkipTagged]
+            struct MyStruct {
+                a: int32
+                tag(1) b: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("The Slice compiler generated this record class from the Slice struct"));
+        assert!(code.contains("public sealed partial record class MyStruct\n"));
+        assert!(code.contains("public void Encode(ref SliceEncoder encoder)"));
+        assert!(code.contains("public bool Equals(MyStruct? other) =>\n        other is not null &&"));
+        assert!(!code.contains("readonly"));
+    }
+
+    #[test]
+    fn optional_fields_share_a_single_bit_sequence() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32?
+                b: string?
+                c: int32
+                d: bool?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert_eq!(code.matches("encoder.GetBitSequenceWriter(3);").count(), 1);
+        assert_eq!(code.matches("decoder.GetBitSequenceReader(3);").count(), 1);
+        assert!(!code.contains("EncodeTagged"));
+    }
+
+    #[test]
+    fn encoded_size_is_computed_from_field_sizes() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::encodedSize]
+            struct MyStruct {
+                a: int32
+                b: string
+                c: int64?
+                tag(1) d: sequence<int16>?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = "\
+    public readonly int GetEncodedSize(SliceEncoding encoding)
+    {
+        int size = 6;
+        int count;
+        int start;
+        checked
+        {
+            count = global::System.Text.Encoding.UTF8.GetByteCount(this.B);
+            size += GetSizeLength(encoding, count) + count;
+            if (this.C is not null)
+            {
+                size += 8;
+            }
+            if (this.D is not null)
+            {
+                start = size;
+                count = global::System.Linq.Enumerable.Count(this.D);
+                size += GetSizeLength(encoding, count) + 2 * count;
+                size += SliceEncoder.GetVarInt62EncodedSize(1) + GetSizeLength(encoding, size - start);
+            }
+        }
+        return size;
+
+        static int GetSizeLength(SliceEncoding encoding, int value) =>
+            encoding == SliceEncoding.Slice1 ? (value < 255 ? 1 : 5) : SliceEncoder.GetVarUInt62EncodedSize((ulong)value);
+    }";
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn decode_constructor_chains_to_main_constructor() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+                b: string
+                tag(1) c: int32?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = "
+    public MyStruct(ref SliceDecoder decoder)
+        : this(
+            decoder.DecodeInt32(),
+            decoder.DecodeString(),
+            decoder.DecodeTagged(1, (ref SliceDecoder decoder) => (int?)decoder.DecodeInt32()))
+    {
+        decoder.SkipTagged();
+    }";
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn decode_constructor_assigns_fields_when_using_a_bit_sequence() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+                b: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(!code.contains(": this("));
+        assert!(code.contains("var bitSequenceReader = decoder.GetBitSequenceReader(1);"));
+        assert!(code.contains("this.A = decoder.DecodeInt32();"));
+        assert!(code.contains("this.B = bitSequenceReader.Read() ? decoder.DecodeString() : null;"));
+    }
+
+    #[test]
+    fn validate_is_called_by_both_constructors() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::validate]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        // The decode constructor chains to the main constructor, which is the only one that calls `Validate`.
+        assert!(code.contains("        this.A = a;\n        Validate();\n    }"));
+        assert!(code.contains("        decoder.DecodeInt32())\n    {\n        decoder.SkipTagged();\n    }"));
+        assert!(code.contains("partial void Validate();"));
+        assert_eq!(code.matches("Validate();").count(), 2);
+    }
+
+    #[test]
+    fn lifecycle_hooks_are_called_when_decoding_and_encoding() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::lifecycleHooks]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("        decoder.SkipTagged();\n        OnDecoded();\n    }"));
+        assert!(code.contains("{\n        OnEncoding(ref encoder);\n        encoder.EncodeInt32(this.A);"));
+        assert!(code.contains("partial void OnDecoded();"));
+        assert!(code.contains("readonly partial void OnEncoding(ref SliceEncoder encoder);"));
+    }
+
+    #[test]
+    fn copy_constructor_of_validated_record_class_calls_validate() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::class]
+            [cs::validate]
+            struct MyStruct {
+                a: int32
+                [cs::readonlyList]
+                b: sequence<string>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = copy_constructor(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Constructs a new instance of <see cref="MyStruct" /> with the fields of another instance.</summary>
+/// <param name="original">The instance to copy.</param>
+/// <remarks>This constructor is called by <see langword="with" /> expressions before they set any property, so it only validates the copied fields.</remarks>
+private MyStruct(MyStruct original)
+{
+    this.A = original.A;
+    this._b = original._b;
+    Validate();
+}"#;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn copy_constructor_is_only_generated_for_validated_record_classes() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::validate]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(!code.contains("MyStruct original"));
+    }
+
+    #[test]
+    fn stable_hash_code_combines_fields_deterministically() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::stableHash]
+            struct MyStruct {
+                a: int32
+                b: string
+                c: sequence<string?>
+                d: bool?
+                tag(1) e: sequence<int32>?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = "\
+    /// <summary>Computes the hash code of this struct. Unlike the default hash code, it's the same across processes.</summary>
+    /// <returns>The hash code of this struct.</returns>
+    public override readonly int GetHashCode()
+    {
+        unchecked
+        {
+            int hash = 17;
+            hash = (hash * 31) + this.A.GetHashCode();
+            hash = (hash * 31) + GetStableHashCode(this.B);
+            foreach (var element in this.C)
+            {
+                hash = (hash * 31) + GetStableHashCode(element);
+            }
+            hash = (hash * 31) + (this.D?.GetHashCode() ?? 0);
+            if (this.E is not null)
+            {
+                foreach (var element in this.E)
+                {
+                    hash = (hash * 31) + element.GetHashCode();
+                }
+            }
+            return hash;
+        }
+
+        static int GetStableHashCode(string? value)
+        {
+            if (value is null)
+            {
+                return 0;
+            }
+            uint hash = 2166136261;
+            foreach (char c in value)
+            {
+                hash = unchecked((hash ^ c) * 16777619);
+            }
+            return unchecked((int)hash);
+        }
+    }";
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn delegates_use_encode_method_and_decode_constructor() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::delegates]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = r#"/// <summary>Encodes a <see cref="MyStruct" /> with a Slice encoder.</summary>
+    public static readonly EncodeAction<MyStruct> SliceEncode =
+        (ref SliceEncoder encoder, MyStruct value) => value.Encode(ref encoder);
+
+    /// <summary>Decodes a <see cref="MyStruct" /> from a Slice decoder, using its decode constructor.</summary>
+    public static readonly DecodeFunc<MyStruct> SliceDecode =
+        (ref SliceDecoder decoder) => new MyStruct(ref decoder);"#;
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn hide_generated_plumbing_only_hides_encoding_members() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            hide_generated_plumbing: true,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        let attribute =
+            "[global::System.ComponentModel.EditorBrowsable(global::System.ComponentModel.EditorBrowsableState.Never)]";
+        assert_eq!(code.matches(attribute).count(), 2);
+        assert!(code.contains(&format!("{attribute}\n    public MyStruct(ref SliceDecoder decoder)")));
+        assert!(code.contains(&format!(
+            "{attribute}\n    public readonly void Encode(ref SliceEncoder encoder)"
+        )));
+    }
+
+    #[test]
+    fn hide_generated_plumbing_marks_encoding_members_as_compiler_generated() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            hide_generated_plumbing: true,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        // The main constructor isn't plumbing, so only the decode constructor and `Encode` are marked.
+        let attribute = "[global::System.Runtime.CompilerServices.CompilerGenerated]";
+        let editor_browsable =
+            "[global::System.ComponentModel.EditorBrowsable(global::System.ComponentModel.EditorBrowsableState.Never)]";
+        assert_eq!(code.matches(attribute).count(), 2);
+        assert!(code.contains(&format!(
+            "{attribute}\n    {editor_browsable}\n    public MyStruct(ref SliceDecoder decoder)"
+        )));
+        assert!(code.contains(&format!(
+            "{attribute}\n    {editor_browsable}\n    public readonly void Encode(ref SliceEncoder encoder)"
+        )));
+    }
+
+    #[test]
+    fn debugger_display_shows_the_first_two_scalar_fields() {
+        // Arrange
+        let slice = "
+            module Test
+
+            enum Color : uint8 {
+                Red
+                Green
+            }
+
+            struct MyStruct {
+                a: Sequence<int32>
+                b: int32
+                c: Color
+                d: string
+            }
+
+            struct NoScalars {
+                a: Sequence<int32>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let no_scalars_def = ast.find_element::<Struct>("Test::NoScalars").unwrap();
+        let options = CsOptions {
+            debugger_display: true,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+        let no_scalars_code = generate_struct(no_scalars_def, &options).to_string();
+
+        // Assert
+        assert!(code.contains(r#"[global::System.Diagnostics.DebuggerDisplay("B = {B}, C = {C}")]"#));
+        assert!(!no_scalars_code.contains("DebuggerDisplay"));
+    }
+
+    #[test]
+    fn generated_code_attribute_contains_tool_version() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {}
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = format!(
+            "[global::System.CodeDom.Compiler.GeneratedCode(\"slicec-cs\", \"{}\")]\npublic partial record struct MyStruct",
+            env!("CARGO_PKG_VERSION"),
+        );
+        assert!(code.contains(&expected));
+    }
+
+    #[test]
+    fn memory_attribute_maps_sequences_to_read_only_memory() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: [cs::memory] Sequence<int32>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public global::System.ReadOnlyMemory<int> A { get; set; }"));
+        assert!(code.contains(": this(\n            decoder.DecodeSequence<int>("));
+        assert!(code.contains("encoder.EncodeSpan(this.A.Span);"));
+    }
+
+    #[test]
+    fn frozen_attribute_maps_dictionaries_to_frozen_dictionaries() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: [cs::frozen] Dictionary<string, int32>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public global::System.Collections.Frozen.FrozenDictionary<string, int> A { get; set; }"));
+        assert!(code.contains(
+            ": this(\n            global::System.Collections.Frozen.FrozenDictionary.ToFrozenDictionary(decoder.DecodeDictionary(\n"
+        ));
+        assert!(code.contains("size => new global::System.Collections.Generic.Dictionary<string, int>(size)"));
+        assert!(code.contains("encoder.EncodeDictionary("));
+    }
+
+    #[test]
+    fn readonly_list_fields_are_backed_by_arrays() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                [cs::readonlyList]
+                values: Sequence<string>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected_property = "\
+    private readonly string[] _values;
+
+    public global::System.Collections.Generic.IReadOnlyList<string> Values
+    {
+        get => _values;
+        init => _values = global::System.Linq.Enumerable.ToArray(value);
+    }";
+        assert!(code.contains(expected_property));
+        assert!(code.contains("public MyStruct(global::System.Collections.Generic.IEnumerable<string> values)"));
+        assert!(code.contains("this._values = global::System.Linq.Enumerable.ToArray(values);"));
+        assert!(code.contains("this._values = decoder.DecodeSequence("));
+        assert!(code.contains("encoder.EncodeSequence(\n            this._values,"));
+    }
+
+    #[test]
+    fn custom_field_types_use_the_provided_converters() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                [cs::type(\"global::System.Guid\")]
+                [cs::encode(\"GuidConverter.Encode\")]
+                [cs::decode(\"GuidConverter.Decode\")]
+                id: Sequence<uint8>
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public global::System.Guid Id { get; set; }"));
+        assert!(code.contains("public MyStruct(global::System.Guid id)"));
+        assert!(code.contains(": this(\n            GuidConverter.Decode(ref decoder))"));
+        assert!(code.contains("GuidConverter.Encode(ref encoder, this.Id);"));
+    }
+
+    #[test]
+    fn fast_equals_takes_the_other_struct_by_in_reference() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::fastEquals]
+            struct MyStruct {
+                a: int32
+                b: string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected = "\
+    public readonly bool Equals(in MyStruct other) =>
+        global::System.Collections.Generic.EqualityComparer<int>.Default.Equals(this.A, other.A) &&
+        global::System.Collections.Generic.EqualityComparer<string>.Default.Equals(this.B, other.B);";
+        assert!(code.contains(expected));
+    }
+
+    #[test]
+    fn fields_named_after_keywords_are_escaped() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                event: string
+                [cs::identifier(\"class\")]
+                kind: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public string Event { get; set; }"));
+        assert!(code.contains("public int @class { get; set; }"));
+        assert!(code.contains("public MyStruct(\n        string @event,\n        int @class)"));
+        assert!(code.contains("this.Event = @event;\n        this.@class = @class;"));
+        assert!(code.contains("decoder.DecodeString(),\n            decoder.DecodeInt32())"));
+        assert!(code.contains("encoder.EncodeInt32(this.@class);"));
+    }
+
+    #[test]
+    fn encode_order_reorders_encoding_and_decoding_but_not_declarations() {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                [cs::encodeOrder(1)] a: int32
+                [cs::encodeOrder(0)] b: string
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        assert!(code.contains("public int A { get; set; }\n\n    public string B { get; set; }"));
+        assert!(code.contains("public MyStruct(\n        int a,\n        string b)"));
+        assert!(code.contains("this.B = decoder.DecodeString();\n        this.A = decoder.DecodeInt32();"));
+        assert!(code.contains("encoder.EncodeString(this.B);\n        encoder.EncodeInt32(this.A);"));
+    }
+
+    #[test]
+    fn framed_structs_encode_and_decode_a_length_prefix() {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::framed]
+            struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let code = generate_struct(struct_def, &CsOptions::default()).to_string();
+
+        // Assert
+        let expected_encode = "\
+        global::System.Span<byte> framePlaceholder = encoder.GetPlaceholderSpan(4);
+        int frameStartPos = encoder.EncodedByteCount;
+        encoder.EncodeInt32(this.A);
+        encoder.EncodeVarInt32(Slice2Definitions.TagEndMarker);
+        SliceEncoder.EncodeInt32(encoder.EncodedByteCount - frameStartPos, framePlaceholder);";
+        assert!(code.contains(expected_encode));
+        assert!(code.contains(
+            "public MyStruct(ref SliceDecoder decoder)\n    {\n        int frameSize = decoder.DecodeInt32();"
+        ));
+        assert!(
+            code.contains("long frameEndPos = decoder.Consumed + frameSize;\n        this.A = decoder.DecodeInt32();")
+        );
+        assert!(code.contains("decoder.SkipTagged();\n        if (decoder.Consumed != frameEndPos)"));
+    }
+
+    #[test_case(false; "without skip locals init")]
+    #[test_case(true; "with skip locals init")]
+    fn skip_locals_init_is_applied_to_the_encode_method_and_decode_constructor(skip_locals_init: bool) {
+        // Arrange
+        let slice = "
+            module Test
+
+            struct MyStruct {
+                a: int32
+                b: string?
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            skip_locals_init,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        let attribute = "[global::System.Runtime.CompilerServices.SkipLocalsInit]";
+        let expected_count = if skip_locals_init { 2 } else { 0 };
+        assert_eq!(code.matches(attribute).count(), expected_count);
+        assert_eq!(code.matches("<c>AllowUnsafeBlocks</c>").count(), expected_count);
+        assert_eq!(
+            code.contains(&format!("{attribute}\n    public MyStruct(ref SliceDecoder decoder)")),
+            skip_locals_init,
+        );
+        assert_eq!(
+            code.contains(&format!(
+                "{attribute}\n    public readonly void Encode(ref SliceEncoder encoder)"
+            )),
+            skip_locals_init,
+        );
+    }
+
+    #[test_case(TargetFramework::Net6; "net6.0")]
+    #[test_case(TargetFramework::Net8; "net8.0")]
+    fn target_framework_gates_features_introduced_after_net6(target_framework: TargetFramework) {
+        // Arrange
+        let slice = "
+            module Test
+
+            [cs::fieldTags]
+            [cs::formattable]
+            struct MyStruct {
+                [cs::required] a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            target_framework,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        let is_net8 = target_framework == TargetFramework::Net8;
+        assert_eq!(code.contains("public required int A { get; set; }"), is_net8);
+        assert_eq!(code.contains("public int A { get; set; }"), !is_net8);
+        assert_eq!(code.contains("SetsRequiredMembers"), is_net8);
+        assert_eq!(code.contains("global::System.IParsable<MyStruct>"), is_net8);
+        assert_eq!(code.contains("FrozenDictionary"), is_net8);
+    }
+
+    #[test_case(false, 0; "without aggressive inlining")]
+    #[test_case(true, 1; "with aggressive inlining")]
+    fn aggressive_inlining_is_applied_to_the_encode_method_only(aggressive_inlining: bool, expected_count: usize) {
+        // Arrange
+        let slice = "
+            module Test
+
+            compact struct MyStruct {
+                a: int32
+            }
+        ";
+        let ast = compile_for_ast(slice);
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+        let options = CsOptions {
+            aggressive_inlining,
+            ..CsOptions::default()
+        };
+
+        // Act
+        let code = generate_struct(struct_def, &options).to_string();
+
+        // Assert
+        let attribute = "[global::System.Runtime.CompilerServices.MethodImpl(global::System.Runtime.CompilerServices.MethodImplOptions.AggressiveInlining)]";
+        assert_eq!(code.matches(attribute).count(), expected_count);
+        assert_eq!(
+            code.matches(&format!(
+                "{attribute}\n    public readonly void Encode(ref SliceEncoder encoder)"
+            ))
+            .count(),
+            expected_count,
+        );
     }
 }
@@ -19,12 +19,16 @@ mod attribute_tests;
 
 use crate::cs_options::{CsOptions, RpcProvider};
 use clap::Parser;
-use cs_compile::{cs_patcher, cs_validator};
+use cs_compile::{check_target_framework_support, cs_patcher, cs_validator};
 use cs_options::SLICEC_CS;
-use generators::generate_from_slice_file;
-use slicec::diagnostics::{Diagnostic, Error};
+use generators::{
+    generate_assembly_info, generate_from_slice_file, generate_plumbing_from_slice_file, generate_single_file,
+};
+use slicec::compilation_state::CompilationState;
+use slicec::diagnostics::{Diagnostic, DiagnosticLevel, Diagnostics, Error};
 use slicec::grammar::Encoding;
 use slicec::slice_file::SliceFile;
+use slicec::slice_options::SliceOptions;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
@@ -36,25 +40,68 @@ pub fn main() {
     let slice_options = &cs_options.slice_options;
 
     let mut compilation_state = slicec::compile_from_options(slice_options, cs_patcher, cs_validator);
-
-    if !compilation_state.diagnostics.has_errors() && !slice_options.dry_run {
-        for slice_file in compilation_state.files.iter().filter(|file| file.is_source) {
-            let code = generate_from_slice_file(slice_file, false, &cs_options);
+    check_target_framework_support(&mut compilation_state, &cs_options);
+
+    let warnings_as_errors =
+        cs_options.warnings_as_errors && has_reported_warnings(&mut compilation_state, slice_options);
+
+    if !compilation_state.diagnostics.has_errors() && !warnings_as_errors && !slice_options.dry_run {
+        if let Some(output_file) = &cs_options.output_single_file {
+            let source_files = compilation_state
+                .files
+                .iter()
+                .filter(|file| file.is_source)
+                .collect::<Vec<_>>();
+            let code = generate_single_file(&source_files, &cs_options);
             write_code(
-                &slice_file.filename,
+                output_file.trim_end_matches(".cs"),
                 &slice_options.output_dir,
                 &code,
                 &mut compilation_state.diagnostics,
             );
+        } else {
+            let source_files = compilation_state
+                .files
+                .iter()
+                .filter(|file| file.is_source)
+                .collect::<Vec<_>>();
+            if let Some(code) = generate_assembly_info(&source_files, &cs_options) {
+                write_code(
+                    "SliceAssemblyInfo",
+                    &slice_options.output_dir,
+                    &code,
+                    &mut compilation_state.diagnostics,
+                );
+            }
 
-            if cs_options.rpc_provider == RpcProvider::IceRpc {
-                let interface_code = generate_from_slice_file(slice_file, true, &cs_options);
+            for slice_file in compilation_state.files.iter().filter(|file| file.is_source) {
+                let code = generate_from_slice_file(slice_file, false, &cs_options);
                 write_code(
-                    &format!("{}.IceRpc", &slice_file.filename),
+                    &slice_file.filename,
                     &slice_options.output_dir,
-                    &interface_code,
+                    &code,
                     &mut compilation_state.diagnostics,
                 );
+
+                if cs_options.split_generated {
+                    let plumbing_code = generate_plumbing_from_slice_file(slice_file, &cs_options);
+                    write_code(
+                        &format!("{}.Slice", &slice_file.filename),
+                        &slice_options.output_dir,
+                        &plumbing_code,
+                        &mut compilation_state.diagnostics,
+                    );
+                }
+
+                if cs_options.rpc_provider == RpcProvider::IceRpc {
+                    let interface_code = generate_from_slice_file(slice_file, true, &cs_options);
+                    write_code(
+                        &format!("{}.IceRpc", &slice_file.filename),
+                        &slice_options.output_dir,
+                        &interface_code,
+                        &mut compilation_state.diagnostics,
+                    );
+                }
             }
         }
     }
@@ -79,9 +126,32 @@ pub fn main() {
     // Emit diagnostics and totals.
     let exit_code = i32::from(compilation_state.emit_diagnostics(slice_options));
 
+    // Warnings don't make the compilation fail on their own, so we fail it here if they're treated as errors.
+    let exit_code = if warnings_as_errors {
+        eprintln!("error: no code was generated, since warnings are treated as errors ('--warnings-as-errors')");
+        exit_code.max(1)
+    } else {
+        exit_code
+    };
+
     std::process::exit(exit_code);
 }
 
+/// Returns true if any warning will be reported for this compilation. Like `emit_diagnostics`, this only checks the
+/// diagnostics left after slicec has applied the lints allowed with `--allow` and with `allow` attributes.
+fn has_reported_warnings(compilation_state: &mut CompilationState, slice_options: &SliceOptions) -> bool {
+    // Updating the diagnostics consumes them, so we put them back for `emit_diagnostics`, which updates them again.
+    let diagnostics = std::mem::replace(&mut compilation_state.diagnostics, Diagnostics::new());
+    let diagnostics = diagnostics.into_updated(&compilation_state.ast, &compilation_state.files, slice_options);
+    let has_warnings = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.level() == DiagnosticLevel::Warning);
+    for diagnostic in diagnostics {
+        diagnostic.push_into(&mut compilation_state.diagnostics);
+    }
+    has_warnings
+}
+
 fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
     let mut file = File::create(path)?;
     file.write_all(contents.as_bytes())
@@ -114,3 +184,43 @@ fn write_code(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::has_reported_warnings;
+    use crate::cs_compile::{cs_patcher, cs_validator};
+    use crate::cs_options::CsOptions;
+    use test_case::test_case;
+
+    #[test_case("", &[], true; "not allowed")]
+    #[test_case("[allow(BrokenDocLink)]", &[], false; "allowed by attribute")]
+    #[test_case("", &["BrokenDocLink"], false; "allowed by option")]
+    #[test_case("", &["All"], false; "all allowed by option")]
+    fn only_warnings_which_are_not_allowed_are_reported(attribute: &str, allowed_lints: &[&str], expected: bool) {
+        // Arrange
+        let slice = format!(
+            "
+            module Test
+
+            /// @see Missing
+            {attribute}
+            struct MyStruct {{}}
+            "
+        );
+        let mut cs_options = CsOptions::default();
+        cs_options
+            .slice_options
+            .allowed_lints
+            .extend(allowed_lints.iter().map(|lint| lint.to_string()));
+        let slice_options = &cs_options.slice_options;
+        let mut compilation_state =
+            slicec::compile_from_strings(&[&slice], Some(slice_options), cs_patcher, cs_validator);
+
+        // Act
+        let has_warnings = has_reported_warnings(&mut compilation_state, slice_options);
+
+        // Assert
+        assert_eq!(has_warnings, expected);
+        assert_eq!(compilation_state.diagnostics.iter().count(), 1);
+    }
+}
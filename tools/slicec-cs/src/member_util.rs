@@ -2,8 +2,10 @@
 
 use crate::code_block::CodeBlock;
 use crate::comments::CommentTag;
+use crate::cs_attributes::{CsDataContract, CsDeepClone, CsType};
+use crate::cs_options::CsOptions;
 use crate::slicec_ext::*;
-use slicec::grammar::{Contained, Field, Member};
+use slicec::grammar::{AttributeFunctions, Contained, Entities, Entity, Field, Member, NamedSymbol, Types};
 
 /// Takes a list of members and sorts them in the following order: [required members][tagged members]
 /// Required members are left in the provided order. Tagged members are sorted so tag values are in increasing order.
@@ -21,32 +23,180 @@ pub fn escape_parameter_name(parameters: &[&impl Member], name: &str) -> String
     }
 }
 
-pub fn field_declaration(field: &Field) -> String {
-    let type_string = field.data_type().field_type_string(&field.namespace());
+pub fn field_declaration(field: &Field, options: &CsOptions) -> String {
+    let type_string = field.property_type_string(&field.namespace());
     let mut prelude = CodeBlock::default();
 
     if let Some(summary) = field.formatted_doc_comment_summary() {
         prelude.writeln(&CommentTag::new("summary", summary))
     }
 
+    if let Some(remarks) = field.formatted_doc_comment_remarks() {
+        prelude.writeln(&CommentTag::new("remarks", remarks))
+    }
+
     for cs_attribute in field.cs_attributes() {
         writeln!(prelude, "[{cs_attribute}]")
     }
 
+    if let Some(data_member) = data_member_attribute(field) {
+        writeln!(prelude, "[{data_member}]");
+    }
+
+    if options.emit_nullable_context_per_member {
+        for nullable_attribute in nullable_attributes(field) {
+            writeln!(
+                prelude,
+                "[global::System.Diagnostics.CodeAnalysis.{nullable_attribute}]"
+            );
+        }
+    }
+
     if let Some(obsolete) = field.obsolete_attribute() {
         writeln!(prelude, "[{obsolete}]");
     }
 
+    let access = field.parent().access_modifier();
+    let required = match is_required_member(field, options) {
+        true => " required",
+        false => "",
+    };
+    let name = field.field_name();
+
+    // Read-only lists are stored in an array, which is copied from the list when the property is initialized.
+    if field.is_cs_readonly_list() {
+        let backing_field = field.backing_field_name();
+        let array_type = field.data_type().incoming_parameter_type_string(&field.namespace());
+        return format!(
+            "\
+private readonly {array_type} {backing_field};
+
+{prelude}
+{access}{required} {type_string} {name}
+{{
+    get => {backing_field};
+    init => {backing_field} = global::System.Linq.Enumerable.ToArray(value);
+}}"
+        );
+    }
+
     format!(
         "\
 {prelude}
-{access} {type_string} {name} {{ get; {setter}; }}",
-        access = field.parent().access_modifier(),
-        name = field.field_name(),
-        setter = if field.is_cs_readonly() { "init" } else { "set" },
+{access}{required} {type_string} {name} {{ get; {setter}; }}",
+        setter = match field.is_cs_readonly() || field.is_cs_init() {
+            true => "init",
+            false => "set",
+        },
     )
 }
 
+/// Returns true if this field is generated as a `required` property. Fields marked with `cs::required` are generated as
+/// regular properties when targeting a framework without required members.
+pub fn is_required_member(field: &Field, options: &CsOptions) -> bool {
+    field.is_cs_required() && options.target_framework.supports_modern_features()
+}
+
+/// Returns the `DataMember` attribute of a field whose parent struct is marked with `cs::dataContract`, or `None` for
+/// any other field. The attribute's order is the field's position in the struct, and tagged fields aren't required.
+fn data_member_attribute(field: &Field) -> Option<String> {
+    let Entities::Struct(struct_def) = field.parent().concrete_entity() else {
+        return None;
+    };
+    if !struct_def.has_attribute::<CsDataContract>() {
+        return None;
+    }
+
+    let order = struct_def
+        .fields()
+        .iter()
+        .position(|other| other.identifier() == field.identifier())
+        .unwrap();
+    let is_required = match field.is_tagged() {
+        true => ", IsRequired = false",
+        false => "",
+    };
+    Some(format!(
+        "global::System.Runtime.Serialization.DataMember(Order = {order}{is_required})"
+    ))
+}
+
+/// Returns the nullability attributes of a field, for files generated without a nullable context. Optional fields,
+/// which includes all tagged fields, may hold null, while other fields never do. Value types don't need attributes,
+/// since their nullability is part of their type.
+fn nullable_attributes(field: &Field) -> &'static [&'static str] {
+    let data_type = field.data_type();
+    match (data_type.is_value_type(), data_type.is_optional) {
+        (true, _) => &[],
+        (false, true) => &["AllowNull", "MaybeNull"],
+        (false, false) => &["NotNull"],
+    }
+}
+
+/// Returns true if this field is mapped to a collection whose contents can be modified after construction: sequences
+/// that aren't read-only lists or read-only memory, and dictionaries that aren't frozen. Fields mapped to a custom type
+/// with `cs::type` are never considered mutable collections, since we don't know anything about their type.
+pub fn is_mutable_collection(field: &Field) -> bool {
+    let data_type = field.data_type();
+    match data_type.concrete_type() {
+        _ if field.has_attribute::<CsType>() || data_type.has_attribute::<CsType>() => false,
+        Types::Sequence(_) => !field.is_cs_readonly_list() && !data_type.is_read_only_memory(),
+        Types::Dictionary(_) => !data_type.is_frozen_dictionary(),
+        _ => false,
+    }
+}
+
+/// Returns an expression which copies the value of a field, for use by `DeepClone`. Mutable collections are copied into
+/// new collections, and structs marked with `cs::deepClone` are deep-cloned. All other values are copied as-is, which
+/// includes the elements of copied collections.
+pub fn field_copy_expression(field: &Field) -> String {
+    let data_type = field.data_type();
+    let value = format!("this.{}", field.field_name());
+
+    let copy = match data_type.concrete_type() {
+        Types::Sequence(_) if is_mutable_collection(field) => {
+            format!("global::System.Linq.Enumerable.ToArray({value})")
+        }
+        Types::Dictionary(dictionary_def) if is_mutable_collection(field) => {
+            let namespace = field.namespace();
+            format!(
+                "new global::System.Collections.Generic.Dictionary<{}, {}>({value})",
+                dictionary_def.key_type.field_type_string(&namespace),
+                dictionary_def.value_type.field_type_string(&namespace),
+            )
+        }
+        Types::Struct(struct_def)
+            if struct_def.has_attribute::<CsDeepClone>() && !data_type.has_attribute::<CsType>() =>
+        {
+            return match data_type.is_optional {
+                true => format!("{value}?.DeepClone()"),
+                false => format!("{value}.DeepClone()"),
+            };
+        }
+        _ => return value,
+    };
+
+    match data_type.is_optional {
+        true => format!("{value} is null ? null : {copy}"),
+        false => copy,
+    }
+}
+
+/// Returns the C# default values to use for the parameters corresponding to the provided fields, with one entry per
+/// field. Since parameters with default values must come after all other parameters in C#, only the trailing run of
+/// optional fields get a default value (of `null`). All other fields don't have a default value, and must be provided.
+pub fn field_default_values(fields: &[&Field]) -> Vec<Option<&'static str>> {
+    let optional_count = fields
+        .iter()
+        .rev()
+        .take_while(|field| field.data_type().is_optional)
+        .count();
+
+    let mut default_values = vec![None; fields.len() - optional_count];
+    default_values.resize(fields.len(), Some("null"));
+    default_values
+}
+
 pub fn initialize_required_fields(fields: &[&Field]) -> CodeBlock {
     // This helper should only be used for classes and exceptions
 
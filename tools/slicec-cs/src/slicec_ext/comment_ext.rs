@@ -5,15 +5,28 @@ use crate::comments::CommentTag;
 use crate::cs_util::format_comment_message;
 use slicec::grammar::Commentable;
 
+/// Formats the tags of doc comments into C# doc comment tags. Only the tags parsed by slicec can be mapped: overviews,
+/// '@param', '@returns', '@throws' and '@see'. Other tags, such as '@example', are reported as unknown by slicec.
 pub trait CommentExt: Commentable {
-    /// If this entity has a doc comment with an overview on it, this returns it with any links resolved to the
-    /// appropriate C# tag. Otherwise this returns `None`.
+    /// If this entity has a doc comment with an overview on it, this returns the overview's first paragraph with any
+    /// links resolved to the appropriate C# tag. Otherwise this returns `None`.
     fn formatted_doc_comment_summary(&self) -> Option<String> {
         self.comment().and_then(|comment| {
-            comment
-                .overview
-                .as_ref()
-                .map(|overview| format_comment_message(overview, &self.namespace()))
+            comment.overview.as_ref().map(|overview| {
+                let formatted_overview = format_comment_message(overview, &self.namespace());
+                split_overview(&formatted_overview).0.to_owned()
+            })
+        })
+    }
+
+    /// If this entity has a doc comment with an overview that spans multiple paragraphs, this returns every paragraph
+    /// after the first with any links resolved to the appropriate C# tag. Otherwise this returns `None`.
+    fn formatted_doc_comment_remarks(&self) -> Option<String> {
+        self.comment().and_then(|comment| {
+            comment.overview.as_ref().and_then(|overview| {
+                let formatted_overview = format_comment_message(overview, &self.namespace());
+                split_overview(&formatted_overview).1.map(str::to_owned)
+            })
         })
     }
 
@@ -44,6 +57,200 @@ pub trait CommentExt: Commentable {
         }
         comments
     }
+
+    /// Returns this entity's returns doc comments, formatted as a single C# returns doc comment tag. Any links present
+    /// in these are resolved to the appropriate C# tag. If multiple returns tags are present, the tag describes the
+    /// returned tuple as a bulleted list. If no returns doc comment is present on this entity, this returns `None`.
+    fn formatted_doc_comment_returns(&self) -> Option<CommentTag> {
+        let comment = self.comment()?;
+        let namespace = self.namespace();
+
+        match comment.returns.as_slice() {
+            // Do nothing if there's no return tags.
+            [] => None,
+
+            // If there's a single return tag, generate a normal `returns` message.
+            [single] => {
+                let message = format_comment_message(&single.message, &namespace);
+                Some(CommentTag::new("returns", message))
+            }
+
+            // If there's multiple return tags, the generated method returns a tuple.
+            // We generate a returns message that lists the elements of the tuple as a bulleted list.
+            multiple => {
+                let mut content = "A tuple containing:\n<list type=\"bullet\">\n".to_owned();
+                for return_tag in multiple {
+                    // TODO add references to the types/identifiers here later!
+                    let message = format_comment_message(&return_tag.message, &namespace);
+                    content = content + "<item><description>" + message.trim_end() + "</description></item>\n";
+                }
+                content += "</list>\n";
+
+                Some(CommentTag::new("returns", content))
+            }
+        }
+    }
+
+    /// Returns this entity's throws doc comments, formatted as a list of C# exception doc comment tags. Any links
+    /// present in these are resolved to the appropriate C# tag. If no throws doc comment is present on this entity,
+    /// this returns an empty vector.
+    fn formatted_doc_comment_exceptions(&self) -> Vec<CommentTag> {
+        let mut comments = Vec::new();
+        if let Some(comment) = self.comment() {
+            // Add an exception comment tag for each '@throws' tag in the comment.
+            for throws_tag in &comment.throws {
+                let message = format_comment_message(&throws_tag.message, &self.namespace());
+                match throws_tag.thrown_type() {
+                    Ok(exception) => {
+                        let name = exception.escape_scoped_identifier(&self.namespace());
+                        comments.push(CommentTag::with_tag_attribute("exception", "cref", &name, message));
+                    }
+                    Err(identifier) => {
+                        // If there was an error resolving the link, print the identifier without any formatting.
+                        let name = &identifier.value;
+                        comments.push(CommentTag::with_tag_attribute("exception", "cref", name, message));
+                    }
+                }
+            }
+        }
+        comments
+    }
 }
 
 impl<T: Commentable + ?Sized> CommentExt for T {}
+
+/// Splits a formatted overview at its first blank line, returning the first paragraph and the remaining paragraphs (if
+/// there are any). Blank lines inside of `<code>` tags don't end a paragraph.
+fn split_overview(overview: &str) -> (&str, Option<&str>) {
+    let mut offset = 0;
+    for line in overview.split_inclusive('\n') {
+        let preceding = &overview[..offset];
+        let is_in_code_block = preceding.matches("<code>").count() > preceding.matches("</code>").count();
+
+        if line.trim().is_empty() && !preceding.trim().is_empty() && !is_in_code_block {
+            let remarks = overview[offset..].trim();
+            return (preceding.trim_end(), (!remarks.is_empty()).then_some(remarks));
+        }
+        offset += line.len();
+    }
+    (overview, None)
+}
+
+#[cfg(test)]
+mod formatted_comment_tests {
+    use super::CommentExt;
+    use crate::attribute_tests::compile_without_errors;
+    use crate::cs_options::CsOptions;
+    use slicec::compilation_state::CompilationState;
+    use slicec::diagnostics::DiagnosticLevel;
+    use slicec::grammar::{Operation, Struct};
+    use slicec::test_helpers::diagnostics_from_compilation_state;
+
+    fn compile_slice(slice: &str) -> CompilationState {
+        let options = &CsOptions::default().slice_options;
+        slicec::compile_from_strings(&[slice], Some(options), |_| {}, |_| {})
+    }
+
+    #[test]
+    fn returns_tag_resolves_links() {
+        // Arrange
+        let slice = "
+            module Test
+            interface MyInterface {
+                /// @returns A {@link MyStruct}.
+                myOperation() -> MyStruct
+            }
+            struct MyStruct {}
+        ";
+        let ast = compile_slice(slice).ast;
+        let operation = ast.find_element::<Operation>("Test::MyInterface::myOperation").unwrap();
+
+        // Act
+        let returns_tag = operation.formatted_doc_comment_returns().unwrap();
+
+        // Assert
+        let expected = r#"/// <returns>A <see cref="MyStruct" />.</returns>"#;
+        assert_eq!(returns_tag.to_string(), expected);
+    }
+
+    #[test]
+    fn overview_paragraphs_after_the_first_are_remarks() {
+        // Arrange
+        let slice = "
+            module Test
+
+            /// This is a struct.
+            ///
+            /// It has {@link MyStruct} in its remarks.
+            ///
+            /// And multiple paragraphs.
+            struct MyStruct {}
+        ";
+        let ast = compile_slice(slice).ast;
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let summary = struct_def.formatted_doc_comment_summary().unwrap();
+        let remarks = struct_def.formatted_doc_comment_remarks().unwrap();
+
+        // Assert
+        assert_eq!(summary, "This is a struct.");
+        assert_eq!(
+            remarks,
+            "It has <see cref=\"MyStruct\" /> in its remarks.\n\nAnd multiple paragraphs."
+        );
+    }
+
+    #[test]
+    fn links_resolve_to_overridden_namespaces() {
+        // Arrange
+        let referenced_slice = r#"
+            [cs::namespace("My.Custom.Ns")]
+            module Other
+
+            struct OtherStruct {}
+        "#;
+        let slice = "
+            module Test
+
+            /// This struct uses {@link Other::OtherStruct}.
+            struct MyStruct {}
+        ";
+        let ast = compile_without_errors(&[referenced_slice, slice], &CsOptions::default()).ast;
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let summary = struct_def.formatted_doc_comment_summary().unwrap();
+
+        // Assert
+        assert_eq!(
+            summary,
+            r#"This struct uses <see cref="global::My.Custom.Ns.OtherStruct" />."#
+        );
+    }
+
+    #[test]
+    fn unresolved_see_tags_are_reported_and_output_as_raw_identifiers() {
+        // Arrange
+        let slice = "
+            module Test
+
+            /// @see Missing
+            struct MyStruct {}
+        ";
+        let options = &CsOptions::default().slice_options;
+        let ast = compile_slice(slice).ast;
+        let struct_def = ast.find_element::<Struct>("Test::MyStruct").unwrap();
+
+        // Act
+        let seealso = struct_def.formatted_doc_comment_seealso();
+        let diagnostics = diagnostics_from_compilation_state(compile_slice(slice), options);
+
+        // Assert
+        assert_eq!(seealso.len(), 1);
+        assert_eq!(seealso[0].to_string(), r#"/// <seealso cref="Missing" />"#);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level(), DiagnosticLevel::Warning);
+        assert!(diagnostics[0].span().is_some());
+    }
+}
@@ -1,8 +1,9 @@
 // Copyright (c) ZeroC, Inc.
 
-use super::{scoped_identifier, InterfaceExt, MemberExt, ModuleExt};
-use crate::cs_attributes::{CsAttribute, CsIdentifier, CsInternal, CsType};
-use crate::cs_util::{escape_keyword, CsCase};
+use super::{scoped_identifier, InterfaceExt, MemberExt, ModuleExt, ParameterExt, TypeRefExt};
+use crate::code_gen_util::TypeContext;
+use crate::cs_attributes::{CsAttribute, CsIdentifier, CsInternal, CsObsoleteError, CsType};
+use crate::cs_util::{escape_keyword, escape_string_literal, CsCase};
 use convert_case::Case;
 use slicec::grammar::attributes::Deprecated;
 use slicec::grammar::*;
@@ -64,17 +65,24 @@ pub trait EntityExt: Entity {
         )
     }
 
+    /// Returns the C# Obsolete attribute for this entity, if it's marked with the Slice deprecated attribute. If this
+    /// entity is also marked with `cs::obsoleteError`, using it is a compile-time error instead of a warning.
     fn obsolete_attribute(&self) -> Option<String> {
         self.find_attribute::<Deprecated>().map(|attribute| {
             let reason = match &attribute.reason {
-                Some(reason) => reason.clone(),
+                Some(reason) => escape_string_literal(reason),
                 None => format!("This {} has been deprecated", self.kind()),
             };
-            format!(r#"global::System.Obsolete("{reason}")"#)
+            match self.has_attribute::<CsObsoleteError>() {
+                true => format!(r#"global::System.Obsolete("{reason}", true)"#),
+                false => format!(r#"global::System.Obsolete("{reason}")"#),
+            }
         })
     }
 
     /// The C# namespace that this entity is contained within.
+    /// Slice only allows declaring types at module scope (interfaces can only contain operations), so this is always
+    /// the namespace of the entity's module, and the generated C# types are never nested in other types.
     fn namespace(&self) -> String {
         self.get_module().as_namespace()
     }
@@ -116,7 +124,8 @@ pub trait EntityExt: Entity {
                 // For operations, we link to the abstract method on the client side interface (ex: `IMyInterface`).
                 let interface_name = operation.parent().scoped_interface_name(namespace);
                 let operation_name = operation.escape_identifier_with_suffix("Async");
-                format!(r#"<see cref="{interface_name}.{operation_name}" />"#)
+                let signature = operation_cref_signature(operation, namespace);
+                format!(r#"<see cref="{interface_name}.{operation_name}{signature}" />"#)
             }
             Entities::Parameter(parameter) => {
                 // Parameter links use a different tag (`paramref`) in C# instead of the normal `see cref` tag.
@@ -148,6 +157,36 @@ pub trait EntityExt: Entity {
 impl<T: Entity + ?Sized> EntityExt for T {}
 
 // Unit tests for the `get_formatted_link` function.
+/// Returns the parameter types of the C# method mapped from the provided operation, formatted for use in a cref.
+/// Generic arguments use braces instead of angle brackets, and reference types don't have nullable annotations, since
+/// neither are allowed in crefs. Links to operations without parameters use the method's name without a signature.
+fn operation_cref_signature(operation: &Operation, namespace: &str) -> String {
+    let parameters = operation.parameters();
+    if parameters.is_empty() {
+        return String::new();
+    }
+
+    let parameter_types = parameters
+        .iter()
+        .map(|parameter| {
+            let type_string = parameter.cs_type_string(namespace, TypeContext::OutgoingParam);
+            let is_nullable_reference_type =
+                parameter.data_type().is_optional && !parameter.data_type().is_value_type() && !parameter.is_streamed;
+            match is_nullable_reference_type {
+                true => type_string.trim_end_matches('?').to_owned(),
+                false => type_string,
+            }
+        })
+        .chain([
+            "IceRpc.Features.IFeatureCollection".to_owned(),
+            "global::System.Threading.CancellationToken".to_owned(),
+        ])
+        .map(|type_string| type_string.replace('<', "{").replace('>', "}"))
+        .collect::<Vec<_>>();
+
+    format!("({})", parameter_types.join(", "))
+}
+
 #[cfg(test)]
 mod formatted_link_tests {
     use super::EntityExt;
@@ -238,6 +277,26 @@ mod formatted_link_tests {
         assert_eq!(operation_link, expected);
     }
 
+    #[test]
+    fn operation_with_parameters() {
+        // Arrange
+        let slice = "
+            module Test
+            interface MyInterface {
+                myOperation(a: int32, b: string?, c: sequence<string>)
+            }
+        ";
+        let ast = compile_slice(slice).ast;
+        let operation = ast.find_element::<Operation>("Test::MyInterface::myOperation").unwrap();
+
+        // Act
+        let operation_link = operation.get_formatted_link("Test");
+
+        // Assert
+        let expected = r#"<see cref="IMyInterface.MyOperationAsync(int, string, global::System.Collections.Generic.IEnumerable{string}, IceRpc.Features.IFeatureCollection, global::System.Threading.CancellationToken)" />"#;
+        assert_eq!(operation_link, expected);
+    }
+
     // Parameters can only be linked to in their operation's doc comment, so there's no need to qualified links.
     #[test]
     fn unqualified_parameter() {
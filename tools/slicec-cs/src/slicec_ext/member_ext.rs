@@ -2,7 +2,7 @@
 
 use super::{EntityExt, TypeRefExt};
 use crate::code_gen_util::TypeContext;
-use crate::cs_attributes::CsReadonly;
+use crate::cs_attributes::{CsInit, CsReadonly, CsReadonlyList, CsRequired, CsType};
 use crate::cs_util::{escape_keyword, format_comment_message};
 use convert_case::Case;
 use slicec::grammar::*;
@@ -32,6 +32,24 @@ pub trait FieldExt {
     /// Check if this field, or its parent struct, are marked with `cs::readonly`.
     fn is_cs_readonly(&self) -> bool;
 
+    /// Check if this field, or its parent struct, are marked with `cs::init`.
+    fn is_cs_init(&self) -> bool;
+
+    /// Check if this field should be mapped to a C# `required` member. This is true for non-optional fields whose
+    /// parent struct is marked with `cs::required`.
+    fn is_cs_required(&self) -> bool;
+
+    /// Check if this field is marked with `cs::readonlyList`. These fields are stored in an array backing field, and
+    /// exposed through an `IReadOnlyList<T>` property.
+    fn is_cs_readonly_list(&self) -> bool;
+
+    /// Returns the name of the private field storing the elements of a `cs::readonlyList` field.
+    fn backing_field_name(&self) -> String;
+
+    /// Returns the C# type of the property generated for this field. This is the type provided with `cs::type` if the
+    /// field is mapped to a custom C# type.
+    fn property_type_string(&self, namespace: &str) -> String;
+
     /// Returns the value of the `@param` doc-comment tag for this enumerator field, if a tag with this field name is
     /// present. Panics if this field is not an enumerator field.
     fn formatted_param_doc_comment(&self) -> Option<String>;
@@ -45,6 +63,39 @@ impl FieldExt for Field {
             .any(|a| a.downcast::<CsReadonly>().is_some())
     }
 
+    fn is_cs_init(&self) -> bool {
+        self.all_attributes()
+            .concat()
+            .into_iter()
+            .any(|a| a.downcast::<CsInit>().is_some())
+    }
+
+    fn is_cs_required(&self) -> bool {
+        !self.data_type.is_optional && self.parent().has_attribute::<CsRequired>()
+    }
+
+    fn is_cs_readonly_list(&self) -> bool {
+        self.has_attribute::<CsReadonlyList>()
+    }
+
+    fn backing_field_name(&self) -> String {
+        format!("_{}", self.cs_identifier(Case::Camel))
+    }
+
+    fn property_type_string(&self, namespace: &str) -> String {
+        if let Some(cs_type) = self.find_attribute::<CsType>() {
+            return cs_type.type_string.clone();
+        }
+
+        match self.data_type.concrete_type() {
+            Types::Sequence(sequence_def) if self.is_cs_readonly_list() => {
+                let element_type = sequence_def.element_type.field_type_string(namespace);
+                format!("global::System.Collections.Generic.IReadOnlyList<{element_type}>")
+            }
+            _ => self.data_type.field_type_string(namespace),
+        }
+    }
+
     fn formatted_param_doc_comment(&self) -> Option<String> {
         if let Entities::Enumerator(enumerator) = self.parent().concrete_entity() {
             // Check if the enumerator has a doc comment on it.
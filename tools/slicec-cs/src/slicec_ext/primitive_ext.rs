@@ -8,6 +8,9 @@ pub trait PrimitiveExt {
 
     /// The C# keyword corresponding to the primitive type.
     fn cs_type(&self) -> &'static str;
+
+    /// Is this primitive mapped to a C# numeric type? These types all implement `ISpanFormattable`.
+    fn is_cs_numeric(&self) -> bool;
 }
 
 impl PrimitiveExt for Primitive {
@@ -54,4 +57,8 @@ impl PrimitiveExt for Primitive {
             Primitive::AnyClass => "SliceClass",
         }
     }
+
+    fn is_cs_numeric(&self) -> bool {
+        !matches!(self, Primitive::Bool | Primitive::String | Primitive::AnyClass)
+    }
 }
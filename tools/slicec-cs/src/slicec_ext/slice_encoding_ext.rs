@@ -3,11 +3,11 @@
 use slicec::grammar::Encoding;
 
 pub trait EncodingExt {
-    fn to_cs_encoding(&self) -> &str;
+    fn to_cs_encoding(&self) -> &'static str;
 }
 
 impl EncodingExt for Encoding {
-    fn to_cs_encoding(&self) -> &str {
+    fn to_cs_encoding(&self) -> &'static str {
         match self {
             Encoding::Slice1 => "SliceEncoding.Slice1",
             Encoding::Slice2 => "SliceEncoding.Slice2",
@@ -1,13 +1,38 @@
 // Copyright (c) ZeroC, Inc.
 
 use super::{EntityExt, EnumExt, PrimitiveExt};
-use crate::cs_attributes::CsType;
+use crate::cs_attributes::{CsClass, CsComparable, CsEncodedSize, CsFrozen, CsMemory, CsStableHash, CsType};
 use slicec::grammar::*;
 
 pub trait TypeRefExt {
     /// Is this type known to map to a C# value type?
     fn is_value_type(&self) -> bool;
 
+    /// Is this type known to map to a blittable C# type? These are types with the same representation in both managed
+    /// and unmanaged memory.
+    fn is_blittable(&self) -> bool;
+
+    /// Is this type known to map to a C# type with a natural ordering? Structs are only comparable if they're marked
+    /// with `cs::comparable`.
+    fn is_comparable(&self) -> bool;
+
+    /// Can the encoded size of this type be computed without encoding it? Structs are only supported if they have a
+    /// fixed size, or are marked with `cs::encodedSize`.
+    fn has_computable_encoded_size(&self) -> bool;
+
+    /// Does this type map to a C# type whose hash code is the same across processes? Strings and sequences are hashed
+    /// by the generated code, and value types use their own hash code. Structs are only supported if they're marked
+    /// with `cs::stableHash`, since the hash codes of other structs combine their fields with process-specific seeds.
+    fn has_stable_hash_code(&self) -> bool;
+
+    /// Is this a sequence marked with `cs::memory`? These sequences are mapped to `ReadOnlyMemory<T>` instead of
+    /// `IList<T>`, and are encoded directly from their underlying span.
+    fn is_read_only_memory(&self) -> bool;
+
+    /// Is this a dictionary marked with `cs::frozen`? These dictionaries are mapped to `FrozenDictionary<TKey, TValue>`
+    /// instead of `IDictionary<TKey, TValue>`, which is frozen once it's fully decoded.
+    fn is_frozen_dictionary(&self) -> bool;
+
     fn field_type_string(&self, namespace: &str) -> String;
     fn incoming_parameter_type_string(&self, namespace: &str) -> String;
     fn outgoing_parameter_type_string(&self, namespace: &str) -> String;
@@ -17,12 +42,82 @@ impl<T: Type + ?Sized> TypeRefExt for TypeRef<T> {
     fn is_value_type(&self) -> bool {
         match self.concrete_type() {
             Types::Primitive(primitive) => !matches!(primitive, Primitive::String | Primitive::AnyClass),
-            Types::Struct(_) => true,
+            Types::Struct(struct_def) => !struct_def.has_attribute::<CsClass>(),
             Types::Enum(enum_ref) => enum_ref.is_mapped_to_cs_enum(),
+            Types::Sequence(_) => self.is_read_only_memory(),
+            _ => false,
+        }
+    }
+
+    fn is_blittable(&self) -> bool {
+        if self.is_optional {
+            return false;
+        }
+
+        match self.concrete_type() {
+            Types::Primitive(primitive) => {
+                !matches!(primitive, Primitive::Bool | Primitive::String | Primitive::AnyClass)
+            }
+            Types::Struct(struct_def) => {
+                !struct_def.has_attribute::<CsClass>()
+                    && struct_def.fields().iter().all(|field| field.data_type().is_blittable())
+            }
+            Types::Enum(enum_def) => enum_def.is_mapped_to_cs_enum(),
+            _ => false,
+        }
+    }
+
+    fn is_comparable(&self) -> bool {
+        match self.concrete_type() {
+            Types::Primitive(primitive) => !matches!(primitive, Primitive::AnyClass),
+            Types::Struct(struct_def) => struct_def.has_attribute::<CsComparable>(),
+            Types::Enum(enum_def) => enum_def.is_mapped_to_cs_enum(),
             _ => false,
         }
     }
 
+    fn has_computable_encoded_size(&self) -> bool {
+        match self.concrete_type() {
+            Types::Primitive(primitive) => {
+                primitive.fixed_wire_size().is_some()
+                    || matches!(
+                        primitive,
+                        Primitive::String
+                            | Primitive::VarInt32
+                            | Primitive::VarUInt32
+                            | Primitive::VarInt62
+                            | Primitive::VarUInt62,
+                    )
+            }
+            Types::Struct(struct_def) => {
+                struct_def.fixed_wire_size().is_some() || struct_def.has_attribute::<CsEncodedSize>()
+            }
+            Types::Enum(enum_def) => enum_def.fixed_wire_size().is_some(),
+            Types::Sequence(sequence_def) => {
+                !sequence_def.element_type.is_optional && sequence_def.element_type.fixed_wire_size().is_some()
+            }
+            _ => false,
+        }
+    }
+
+    fn has_stable_hash_code(&self) -> bool {
+        match self.concrete_type() {
+            Types::Primitive(primitive) => !matches!(primitive, Primitive::AnyClass),
+            Types::Struct(struct_def) => struct_def.has_attribute::<CsStableHash>(),
+            Types::Enum(enum_def) => enum_def.is_mapped_to_cs_enum(),
+            Types::Sequence(sequence_def) => sequence_def.element_type.has_stable_hash_code(),
+            _ => false,
+        }
+    }
+
+    fn is_read_only_memory(&self) -> bool {
+        matches!(self.concrete_type(), Types::Sequence(_)) && self.has_attribute::<CsMemory>()
+    }
+
+    fn is_frozen_dictionary(&self) -> bool {
+        matches!(self.concrete_type(), Types::Dictionary(_)) && self.has_attribute::<CsFrozen>()
+    }
+
     fn field_type_string(&self, namespace: &str) -> String {
         let type_string = match &self.concrete_typeref() {
             TypeRefs::Primitive(primitive_ref) => primitive_ref.cs_type().to_owned(),
@@ -41,12 +136,18 @@ impl<T: Type + ?Sized> TypeRefExt for TypeRef<T> {
             }
             TypeRefs::Sequence(sequence_ref) => {
                 let element_type = sequence_ref.element_type.field_type_string(namespace);
-                format!("global::System.Collections.Generic.IList<{element_type}>")
+                match self.is_read_only_memory() {
+                    true => format!("global::System.ReadOnlyMemory<{element_type}>"),
+                    false => format!("global::System.Collections.Generic.IList<{element_type}>"),
+                }
             }
             TypeRefs::Dictionary(dictionary_ref) => {
                 let key_type = dictionary_ref.key_type.field_type_string(namespace);
                 let value_type = dictionary_ref.value_type.field_type_string(namespace);
-                format!("global::System.Collections.Generic.IDictionary<{key_type}, {value_type}>")
+                match self.is_frozen_dictionary() {
+                    true => format!("global::System.Collections.Frozen.FrozenDictionary<{key_type}, {value_type}>"),
+                    false => format!("global::System.Collections.Generic.IDictionary<{key_type}, {value_type}>"),
+                }
             }
         };
 
@@ -55,25 +156,22 @@ impl<T: Type + ?Sized> TypeRefExt for TypeRef<T> {
 
     fn incoming_parameter_type_string(&self, namespace: &str) -> String {
         let type_string = match &self.concrete_typeref() {
-            TypeRefs::Sequence(sequence_ref) => {
-                match sequence_ref.find_attribute::<CsType>() {
-                    Some(argument) => argument.type_string.clone(),
-                    None => {
-                        let element_type = sequence_ref.element_type.field_type_string(namespace);
-                        format!("{element_type}[]")
-                    }
+            TypeRefs::Sequence(sequence_ref) => match sequence_ref.find_attribute::<CsType>() {
+                Some(argument) => argument.type_string.clone(),
+                None => {
+                    let element_type = sequence_ref.element_type.field_type_string(namespace);
+                    format!("{element_type}[]")
                 }
-            }
-            TypeRefs::Dictionary(dictionary_ref) => {
-                match dictionary_ref.find_attribute::<CsType>() {
-                    Some(argument) => argument.type_string.clone(),
-                    None => {
-                        let key_type = dictionary_ref.key_type.field_type_string(namespace);
-                        let value_type = dictionary_ref.value_type.field_type_string(namespace);
-                        format!("global::System.Collections.Generic.Dictionary<{key_type}, {value_type}>")
-                    }
+            },
+            TypeRefs::Dictionary(dictionary_ref) => match dictionary_ref.find_attribute::<CsType>() {
+                Some(argument) => argument.type_string.clone(),
+                None if self.is_frozen_dictionary() => return self.field_type_string(namespace),
+                None => {
+                    let key_type = dictionary_ref.key_type.field_type_string(namespace);
+                    let value_type = dictionary_ref.value_type.field_type_string(namespace);
+                    format!("global::System.Collections.Generic.Dictionary<{key_type}, {value_type}>")
                 }
-            }
+            },
             _ => return self.field_type_string(namespace),
         };
 
@@ -109,9 +207,24 @@ impl<T: Type + ?Sized> TypeRefExt for TypeRef<T> {
     }
 }
 
+/// Appends a '?' to the provided type string if it's optional. Custom types can be mapped to C# types which are already
+/// nullable (with `cs::type`), so we're careful to not annotate these types twice.
 fn set_optional_modifier_for(type_string: String, is_optional: bool) -> String {
-    match is_optional {
+    match is_optional && !type_string.ends_with('?') {
         true => type_string + "?",
         false => type_string,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::set_optional_modifier_for;
+    use test_case::test_case;
+
+    #[test_case("int", true, "int?"; "optional")]
+    #[test_case("int", false, "int"; "not optional")]
+    #[test_case("Foo?", true, "Foo?"; "already nullable")]
+    fn optional_modifier(type_string: &str, is_optional: bool, expected: &str) {
+        assert_eq!(set_optional_modifier_for(type_string.to_owned(), is_optional), expected);
+    }
+}